@@ -1,11 +1,26 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 
-use crate::error::Result;
+use crate::{
+    config::{Config, NetworkConfig, Profile},
+    error::Result,
+    output::{try_output_format_from_str, OutputFormat},
+};
+
+/// Settings shared by all commands, resolved from the global arguments and the configuration file.
+#[derive(Debug)]
+pub struct Context {
+    pub output: OutputFormat,
+    pub profile: Option<Profile>,
+    /// User-defined networks from the configuration file, keyed by their name.
+    pub networks: HashMap<String, NetworkConfig>,
+}
 
 /// Trait to implement for commands.
 #[async_trait]
 pub trait Command {
-    async fn run(&self) -> Result<()>;
+    async fn run(&self, ctx: &Context) -> Result<()>;
 }
 
 /// CLI tool for interacting with the IOTA Tangle.
@@ -14,24 +29,99 @@ pub trait Command {
     author = "Matthew Maxwell <maxwellmattryan@gmail.com>", 
     version = env!("CARGO_PKG_VERSION")
 )]
-pub enum Cli {
+pub struct Cli {
+    /// Format of the command output ("text" or "json").
+    #[structopt(short, long, global = true, env = "TIO_OUTPUT", parse(try_from_str=try_output_format_from_str))]
+    pub output: Option<OutputFormat>,
+
+    /// Named profile from the configuration file to use.
+    #[structopt(long, global = true, env = "TIO_PROFILE")]
+    pub profile: Option<String>,
+
+    #[structopt(subcommand)]
+    pub command: Commands,
+}
+
+impl Cli {
+    /// Resolve the global arguments and run the given subcommand.
+    pub async fn run(&self) -> Result<()> {
+        let config = Config::load()?;
+        let profile = config.profile(self.profile.as_deref())?;
+        let output = match (self.output, profile.as_ref().and_then(|p| p.output.as_deref())) {
+            (Some(o), _) => o,
+            (None, Some(o)) => try_output_format_from_str(o)?,
+            (None, None) => OutputFormat::default(),
+        };
+        let ctx = Context {
+            output,
+            profile,
+            networks: config.networks,
+        };
+
+        self.command.run(&ctx).await
+    }
+}
+
+/// Available subcommands of the CLI.
+#[derive(structopt::StructOpt)]
+pub enum Commands {
+    /// Show the balance and outputs of an address in the IOTA ledger.
+    Address(crate::commands::AddressCommand),
+
     /// Broadcast a message to the IOTA Tangle.
     Broadcast(crate::commands::BroadcastCommand),
 
     /// Query for node information on the IOTA Tangle.
     Info(crate::commands::InfoCommand),
 
+    /// Manage the RSA keys used to encrypt and decrypt data.
+    Keys(crate::commands::KeysCommand),
+
+    /// Show milestones and the ledger changes they confirmed.
+    Milestone(crate::commands::MilestoneCommand),
+
+    /// Monitor an address for activity in the IOTA ledger.
+    Monitor(crate::commands::MonitorCommand),
+
+    /// Show an output in the IOTA ledger given its ID.
+    Output(crate::commands::OutputCommand),
+
+    /// Measure the proof of work done for messages.
+    Pow(crate::commands::PowCommand),
+
+    /// Promote messages that are not yet confirmed.
+    Promote(crate::commands::PromoteCommand),
+
+    /// Reattach messages that are not yet confirmed.
+    Reattach(crate::commands::ReattachCommand),
+
     /// Search for a message on the IOTA Tangle.
     Search(crate::commands::SearchCommand),
+
+    /// Repeatedly broadcast messages to the IOTA Tangle.
+    Spam(crate::commands::SpamCommand),
+
+    /// Stream events from a node as they happen.
+    Subscribe(crate::commands::SubscribeCommand),
 }
 
 #[async_trait]
-impl Command for Cli {
-    async fn run(&self) -> Result<()> {
+impl Command for Commands {
+    async fn run(&self, ctx: &Context) -> Result<()> {
         match self {
-            Self::Broadcast(c) => c.run().await,
-            Self::Info(c) => c.run().await,
-            Self::Search(c) => c.run().await,
+            Self::Address(c) => c.run(ctx).await,
+            Self::Broadcast(c) => c.run(ctx).await,
+            Self::Info(c) => c.run(ctx).await,
+            Self::Keys(c) => c.run(ctx).await,
+            Self::Milestone(c) => c.run(ctx).await,
+            Self::Monitor(c) => c.run(ctx).await,
+            Self::Output(c) => c.run(ctx).await,
+            Self::Pow(c) => c.run(ctx).await,
+            Self::Promote(c) => c.run(ctx).await,
+            Self::Reattach(c) => c.run(ctx).await,
+            Self::Search(c) => c.run(ctx).await,
+            Self::Spam(c) => c.run(ctx).await,
+            Self::Subscribe(c) => c.run(ctx).await,
         }
     }
 }
@@ -0,0 +1,88 @@
+use async_trait::async_trait;
+
+use crate::{
+    cli::{Command, Context},
+    commands::monitor::try_address_from_str,
+    error::{Error, Result},
+    iota::{
+        client::{ClientArgs, ClientOptions},
+        get_address,
+        quorum::{quorum_read, QuorumArgs},
+    },
+};
+
+fn try_page_from_str(arg: &str) -> Result<usize> {
+    match arg.parse::<usize>() {
+        Ok(p) if p > 0 => Ok(p),
+        _ => Err(Error::PageInvalid(arg.to_string())),
+    }
+}
+
+/// Arguments for the `address` subcommand.
+#[derive(Debug, structopt::StructOpt)]
+pub struct AddressArgs {
+    /// Bech32-encoded address to inspect (e.g. "iota1..." or "atoi1...").
+    #[structopt(parse(try_from_str=try_address_from_str))]
+    pub address: String,
+
+    /// Page of outputs to show, starting at 1.
+    #[structopt(long, default_value = "1", parse(try_from_str=try_page_from_str))]
+    pub page: usize,
+
+    /// Number of outputs to show per page.
+    #[structopt(long, default_value = "20", parse(try_from_str=try_page_from_str))]
+    pub page_size: usize,
+
+    /// Only show outputs that are not yet spent.
+    #[structopt(long)]
+    pub unspent: bool,
+}
+
+/// `address` subcommand that shows the balance and outputs of an address.
+#[derive(structopt::StructOpt)]
+pub struct AddressCommand {
+    #[structopt(flatten)]
+    pub address: AddressArgs,
+
+    #[structopt(flatten)]
+    pub quorum: QuorumArgs,
+
+    #[structopt(flatten)]
+    pub client: ClientArgs,
+}
+
+#[async_trait]
+impl Command for AddressCommand {
+    async fn run(&self, ctx: &Context) -> Result<()> {
+        let client = self.client.unpack_options(ctx.profile.as_ref(), &ctx.networks)?;
+        let args = &self.address;
+
+        let read = |c: ClientOptions| async move {
+            get_address(&args.address, args.page, args.page_size, !args.unspent, &c).await
+        };
+        let summary = match self.quorum.quorum {
+            Some(q) => quorum_read(&client, q, read).await?,
+            None => read(client).await?,
+        };
+
+        ctx.output.print(&summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_page_from_str() {
+        assert_eq!(3, try_page_from_str("3").unwrap());
+        assert_eq!(
+            Error::PageInvalid(String::from("0")),
+            try_page_from_str("0").unwrap_err()
+        );
+        assert_eq!(
+            Error::PageInvalid(String::from("-1")),
+            try_page_from_str("-1").unwrap_err()
+        );
+    }
+}
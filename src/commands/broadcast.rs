@@ -1,10 +1,27 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+    time::Duration,
+};
+
 use arboard::Clipboard;
 use async_trait::async_trait;
+use sha2::{Digest, Sha256};
 
 use crate::{
-    cli::Command,
+    cli::{Command, Context},
+    crypto::{encrypt, try_algorithm_from_str, Algorithm},
     error::{Error, Result},
-    iota::{broadcast_message, client::ClientArgs},
+    iota::{
+        broadcast_message,
+        client::ClientArgs,
+        confirm::wait_for_confirmation,
+        pow::{track_pow, PowArgs},
+        views::MessageStatus,
+    },
+    keys::load_public_key,
+    output::OutputFormat,
 };
 
 /// The maximum number of bytes allowed for a data message's index.
@@ -13,7 +30,7 @@ pub const MAX_INDEX_BYTES: usize = 64;
 /// The maximum number of bytes allowed for a data message's payload.
 pub const MAX_DATA_BYTES: usize = 4096;
 
-fn try_data_from_str(arg: &str) -> Result<String> {
+pub(crate) fn try_data_from_str(arg: &str) -> Result<String> {
     let data = arg.to_string();
     let size = data.as_bytes().len();
 
@@ -23,7 +40,7 @@ fn try_data_from_str(arg: &str) -> Result<String> {
     }
 }
 
-fn try_data_index_from_str(arg: &str) -> Result<String> {
+pub(crate) fn try_data_index_from_str(arg: &str) -> Result<String> {
     let index = arg.to_string();
     let size = index.as_bytes().len();
 
@@ -33,30 +50,154 @@ fn try_data_index_from_str(arg: &str) -> Result<String> {
     }
 }
 
+/// The encodings in which data can be given.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DataEncoding {
+    Utf8,
+    Hex,
+    Base64,
+}
+
+impl DataEncoding {
+    /// Decode the given input into the raw bytes it represents, ignoring surrounding whitespace for hex and base64.
+    pub fn decode(&self, input: &[u8]) -> Result<Vec<u8>> {
+        let text = match std::str::from_utf8(input) {
+            Ok(t) => t,
+            Err(_) => return Err(Error::CannotDecodeData(self.name().to_string())),
+        };
+        let decoded = match self {
+            Self::Utf8 => return Ok(input.to_vec()),
+            Self::Hex => hex::decode(text.trim()).ok(),
+            Self::Base64 => base64::decode(text.trim()).ok(),
+        };
+
+        match decoded {
+            Some(d) => Ok(d),
+            None => Err(Error::CannotDecodeData(self.name().to_string())),
+        }
+    }
+
+    fn name(&self) -> &str {
+        match self {
+            Self::Utf8 => "utf8",
+            Self::Hex => "hex",
+            Self::Base64 => "base64",
+        }
+    }
+}
+
+pub(crate) fn try_data_encoding_from_str(arg: &str) -> Result<DataEncoding> {
+    match arg {
+        "utf8" | "utf-8" => Ok(DataEncoding::Utf8),
+        "hex" => Ok(DataEncoding::Hex),
+        "base64" => Ok(DataEncoding::Base64),
+        _ => Err(Error::DataEncodingInvalid(arg.to_string())),
+    }
+}
+
+/// Validate JSON data and strip its insignificant whitespace.
+fn minify_json(data: &[u8]) -> Result<Vec<u8>> {
+    match serde_json::from_slice::<serde_json::Value>(data) {
+        Ok(v) => Ok(v.to_string().into_bytes()),
+        Err(_) => Err(Error::CannotDecodeData(String::from("JSON"))),
+    }
+}
+
 /// Arguments for the `broadcast` subcommand.
 #[derive(Debug, structopt::StructOpt)]
 pub struct BroadcastArgs {
     /// UTF-8 encoded data embedded inside the indexation payload.
-    #[structopt(parse(try_from_str=try_data_from_str))]
+    // The size is only checked once the data is decoded, minified or hashed.
     pub data: Option<String>,
 
     /// Indexation key used in the IOTA Tangle.
     #[structopt(parse(try_from_str=try_data_index_from_str))]
     pub index: Option<String>,
+
+    /// Indexation key used in the IOTA Tangle, which may be given along with --file or --stdin.
+    #[structopt(
+        long = "index",
+        value_name = "index",
+        conflicts_with = "index",
+        parse(try_from_str=try_data_index_from_str)
+    )]
+    pub named_index: Option<String>,
+
+    /// File whose contents are embedded instead of the data argument.
+    #[structopt(long, conflicts_with_all = &["data", "stdin"])]
+    pub file: Option<PathBuf>,
+
+    /// Read the data from standard input instead of the data argument.
+    #[structopt(long, conflicts_with = "data")]
+    pub stdin: bool,
+
+    /// Encoding of the given data ("utf8", "hex" or "base64"), which is decoded before being embedded.
+    #[structopt(long, parse(try_from_str=try_data_encoding_from_str))]
+    pub encoding: Option<DataEncoding>,
+
+    /// Validate the data as JSON and minify it before it is embedded.
+    #[structopt(long)]
+    pub json: bool,
+
+    /// Embed the SHA-256 hash of the data rather than the data itself.
+    #[structopt(long)]
+    pub hash: bool,
+
+    /// Wait until the message is confirmed, promoting or reattaching it if needed.
+    #[structopt(long)]
+    pub wait: bool,
+
+    /// Number of seconds to wait for the message to be confirmed.
+    #[structopt(long, default_value = "300")]
+    pub timeout: u64,
+
+    /// Alias (or key file) of the RSA public key to encrypt the data for.
+    #[structopt(long, value_name = "key")]
+    pub encrypt_to: Option<String>,
+
+    /// Algorithm used to encrypt the data ("rsa-aes-256-gcm").
+    #[structopt(long, default_value = "rsa-aes-256-gcm", parse(try_from_str=try_algorithm_from_str))]
+    pub algorithm: Algorithm,
 }
 
 impl BroadcastArgs {
-    pub fn unpack_args(&self) -> (&str, &str) {
-        let index = match &self.index {
+    /// Resolve the index and the raw bytes of the data (which is not yet encrypted).
+    pub fn unpack_args(&self) -> Result<(&str, Vec<u8>)> {
+        let index = match self.named_index.as_ref().or_else(|| self.index.as_ref()) {
             Some(i) => i.as_str(),
             None => "tio-cli",
         };
-        let data = match &self.data {
-            Some(d) => d.as_str(),
-            None => "tio-message",
+
+        // Files and standard input are taken as raw bytes unless an encoding is given.
+        let input = if let Some(path) = &self.file {
+            match fs::read(path) {
+                Ok(b) => b,
+                Err(_) => return Err(Error::CannotReadData(path.display().to_string())),
+            }
+        } else if self.stdin {
+            let mut buffer = Vec::new();
+            match io::stdin().read_to_end(&mut buffer) {
+                Ok(_) => buffer,
+                Err(_) => return Err(Error::CannotReadData(String::from("stdin"))),
+            }
+        } else {
+            match &self.data {
+                Some(d) => d.as_bytes().to_vec(),
+                None => b"tio-message".to_vec(),
+            }
+        };
+        let data = match self.encoding {
+            Some(e) => e.decode(&input)?,
+            None => input,
+        };
+        let data = if self.json { minify_json(&data)? } else { data };
+        let data = if self.hash {
+            Sha256::digest(&data).to_vec()
+        } else {
+            data
         };
 
-        (index, data)
+        Ok((index, data))
     }
 }
 
@@ -66,29 +207,102 @@ pub struct BroadcastCommand {
     #[structopt(flatten)]
     pub broadcast: BroadcastArgs,
 
+    #[structopt(flatten)]
+    pub pow: PowArgs,
+
     #[structopt(flatten)]
     pub client: ClientArgs,
 }
 
 #[async_trait]
 impl Command for BroadcastCommand {
-    async fn run(&self) -> Result<()> {
-        let (index, data) = self.broadcast.unpack_args();
-        let node_url = self.client.unpack_url();
+    async fn run(&self, ctx: &Context) -> Result<()> {
+        let (index, data) = self.broadcast.unpack_args()?;
+        let mut client = self.client.unpack_options(ctx.profile.as_ref(), &ctx.networks)?;
+        self.pow.apply_to(&mut client);
+        let show_progress = ctx.output == OutputFormat::Text;
+
+        let mut message = match &self.broadcast.encrypt_to {
+            Some(key) => {
+                let public_key = load_public_key(key)?;
+                let encrypted = encrypt(&data, &public_key, self.broadcast.algorithm)?;
+                if encrypted.len() >= MAX_DATA_BYTES {
+                    return Err(Error::MessageDataTooLarge(encrypted.len()));
+                }
+
+                // The view echoes the data as given rather than the ciphertext.
+                let sent = broadcast_message(index, &encrypted, &client);
+                let mut message = track_pow(sent, client.pow_timeout, show_progress).await?;
+                message.data = data;
+                message.encryption = Some(self.broadcast.algorithm.name().to_string());
+                message
+            }
+            None if data.len() >= MAX_DATA_BYTES => return Err(Error::MessageDataTooLarge(data.len())),
+            None => {
+                let sent = broadcast_message(index, &data, &client);
+                track_pow(sent, client.pow_timeout, show_progress).await?
+            }
+        };
 
-        let message_id = broadcast_message(index, data, node_url).await;
+        if self.broadcast.wait {
+            if ctx.output == OutputFormat::Text {
+                eprintln!(
+                    "Waiting up to {}s for message {} to be confirmed...",
+                    self.broadcast.timeout, message.id
+                );
+            }
+            let mut id = [0u8; 32];
+            if hex::decode_to_slice(&message.id, &mut id).is_err() {
+                return Err(Error::MessageHashInvalid(message.id));
+            }
+            let timeout = Duration::from_secs(self.broadcast.timeout);
+            message.confirmation = Some(wait_for_confirmation(&id, timeout, &client).await?);
+        }
+        ctx.output.print(&message)?;
 
-        let mut clipboard = Clipboard::new().unwrap();
-        clipboard.set_text(message_id.to_string()).unwrap();
+        // Failing to copy the ID (e.g. on a headless machine) should not fail the broadcast itself.
+        let copied = Clipboard::new().and_then(|mut c| c.set_text(message.id.clone()));
+        if copied.is_err() {
+            eprintln!("warning: unable to copy the message ID to the clipboard");
+        }
 
-        Ok(())
+        match message.confirmation {
+            Some(c) if c.status == MessageStatus::Pending => Err(Error::ConfirmationTimeout(self.broadcast.timeout)),
+            Some(c) if c.status == MessageStatus::Conflicting => {
+                let reason = c.metadata.and_then(|m| m.conflict_reason);
+                Err(Error::MessageConflicting(
+                    reason.unwrap_or_else(|| String::from("unknown reason")),
+                ))
+            }
+            _ => Ok(()),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use structopt::StructOpt;
+
     use super::*;
 
+    #[test]
+    fn test_named_index_with_file() {
+        let args = BroadcastArgs::from_iter_safe(&["broadcast", "--file", "x", "--index", "y"]).unwrap();
+        assert_eq!(Some(PathBuf::from("x")), args.file);
+        assert_eq!(Some(String::from("y")), args.named_index);
+
+        let args = BroadcastArgs::from_iter_safe(&["broadcast", "data", "positional"]).unwrap();
+        assert_eq!(Some(String::from("positional")), args.index);
+        assert!(BroadcastArgs::from_iter_safe(&["broadcast", "data", "positional", "--index", "y"]).is_err());
+    }
+
+    #[test]
+    fn test_data_size_checked_after_decoding() {
+        let data = hex::encode(vec![0u8; MAX_DATA_BYTES - 1]);
+        let args = BroadcastArgs::from_iter_safe(&["broadcast", &data, "--encoding", "hex"]).unwrap();
+        assert_eq!(MAX_DATA_BYTES - 1, args.unpack_args().unwrap().1.len());
+    }
+
     #[test]
     fn test_try_index_from_str() {
         let valid_index: &str = "This is a valid index.";
@@ -100,4 +314,31 @@ mod tests {
         let valid_data: &str = "This is valid data.";
         assert_eq!(String::from(valid_data), try_data_from_str(valid_data).unwrap());
     }
+
+    #[test]
+    fn test_data_encoding_decode() {
+        assert_eq!(vec![0xde, 0xad], DataEncoding::Hex.decode(b"dead\n").unwrap());
+        assert_eq!(b"tio".to_vec(), DataEncoding::Base64.decode(b"dGlv").unwrap());
+        assert_eq!(b"tio".to_vec(), DataEncoding::Utf8.decode(b"tio").unwrap());
+        assert_eq!(
+            Error::CannotDecodeData(String::from("utf8")),
+            DataEncoding::Utf8.decode(&[0xff]).unwrap_err()
+        );
+        assert_eq!(
+            Error::DataEncodingInvalid(String::from("binary")),
+            try_data_encoding_from_str("binary").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_minify_json() {
+        assert_eq!(
+            br#"{"event":"login","ok":true}"#.to_vec(),
+            minify_json(b"{\n  \"event\": \"login\",\n  \"ok\": true\n}\n").unwrap()
+        );
+        assert_eq!(
+            Error::CannotDecodeData(String::from("JSON")),
+            minify_json(b"{\"event\":").unwrap_err()
+        );
+    }
 }
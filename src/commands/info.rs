@@ -1,23 +1,35 @@
 use async_trait::async_trait;
 
 use crate::{
-    cli::Command,
+    cli::{Command, Context},
     error::Result,
-    iota::{client::ClientArgs, get_info},
+    iota::{
+        client::ClientArgs,
+        get_info,
+        quorum::{quorum_read, QuorumArgs},
+    },
 };
 
 /// `info` subcommand that queries for node information.
 #[derive(structopt::StructOpt)]
 pub struct InfoCommand {
+    #[structopt(flatten)]
+    pub quorum: QuorumArgs,
+
     #[structopt(flatten)]
     pub client: ClientArgs,
 }
 
 #[async_trait]
 impl Command for InfoCommand {
-    async fn run(&self) -> Result<()> {
-        let node_url = self.client.unpack_url();
+    async fn run(&self, ctx: &Context) -> Result<()> {
+        let client = self.client.unpack_options(ctx.profile.as_ref(), &ctx.networks)?;
+
+        let summary = match self.quorum.quorum {
+            Some(q) => quorum_read(&client, q, |c| async move { get_info(&c).await }).await?,
+            None => get_info(&client).await?,
+        };
 
-        Ok(get_info(node_url).await)
+        ctx.output.print(&summary)
     }
 }
@@ -0,0 +1,278 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, ErrorKind, Write},
+    path::{Path, PathBuf},
+};
+
+use async_trait::async_trait;
+use rand::rngs::OsRng;
+use rsa::RsaPrivateKey;
+
+use crate::{
+    cli::{Command, Context},
+    error::{Error, Result},
+    keys::{
+        read_passphrase, try_alias_from_str, try_key_bits_from_str, try_key_format_from_str, Key, KeyFormat, Keystore,
+    },
+};
+
+/// `keys generate` subcommand that creates a new keypair in the keystore.
+#[derive(structopt::StructOpt)]
+pub struct GenerateKeyCommand {
+    /// Alias to store the key under.
+    #[structopt(parse(try_from_str=try_alias_from_str))]
+    pub alias: String,
+
+    /// Size of the key in bits (2048, 3072 or 4096).
+    #[structopt(long, default_value = "2048", parse(try_from_str=try_key_bits_from_str))]
+    pub bits: usize,
+
+    /// Protect the private key with a passphrase.
+    #[structopt(long)]
+    pub protect: bool,
+}
+
+#[async_trait]
+impl Command for GenerateKeyCommand {
+    async fn run(&self, ctx: &Context) -> Result<()> {
+        let keystore = Keystore::open()?;
+        if keystore.contains(&self.alias) {
+            return Err(Error::KeyAlreadyExists(self.alias.clone()));
+        }
+        let passphrase = if self.protect {
+            Some(read_passphrase("New passphrase: ", true)?)
+        } else {
+            None
+        };
+
+        let bits = self.bits;
+        let private_key = match tokio::task::spawn_blocking(move || RsaPrivateKey::new(&mut OsRng, bits)).await {
+            Ok(Ok(k)) => k,
+            _ => return Err(Error::CannotGenerateKey),
+        };
+        let key = keystore.insert(&self.alias, &Key::Private(private_key), passphrase.as_ref())?;
+
+        ctx.output.print(&key)
+    }
+}
+
+/// `keys import` subcommand that adds an existing PEM- or DER-encoded key to the keystore.
+#[derive(structopt::StructOpt)]
+pub struct ImportKeyCommand {
+    /// Alias to store the key under.
+    #[structopt(parse(try_from_str=try_alias_from_str))]
+    pub alias: String,
+
+    /// File containing the private or public key (PKCS#8, SPKI or PKCS#1).
+    pub file: PathBuf,
+
+    /// Protect the private key with a new passphrase.
+    #[structopt(long)]
+    pub protect: bool,
+}
+
+#[async_trait]
+impl Command for ImportKeyCommand {
+    async fn run(&self, ctx: &Context) -> Result<()> {
+        let keystore = Keystore::open()?;
+        if keystore.contains(&self.alias) {
+            return Err(Error::KeyAlreadyExists(self.alias.clone()));
+        }
+
+        let name = self.file.display().to_string();
+        let bytes = match fs::read(&self.file) {
+            Ok(b) => b,
+            Err(_) => return Err(Error::KeyInvalid(name)),
+        };
+        // An encrypted key stays protected with the same passphrase unless a new one is requested.
+        let mut passphrase = None;
+        let key = Key::parse(&name, &bytes, || {
+            let p = read_passphrase(&format!("Passphrase for {}: ", name), false)?;
+            passphrase = Some(p.clone());
+            Ok(p)
+        })?;
+        if self.protect {
+            passphrase = Some(read_passphrase("New passphrase: ", true)?);
+        }
+
+        let key = keystore.insert(&self.alias, &key, passphrase.as_ref())?;
+
+        ctx.output.print(&key)
+    }
+}
+
+/// `keys export` subcommand that writes a key from the keystore.
+#[derive(structopt::StructOpt)]
+pub struct ExportKeyCommand {
+    /// Alias of the key to export.
+    #[structopt(parse(try_from_str=try_alias_from_str))]
+    pub alias: String,
+
+    /// Export the private key rather than the public key.
+    #[structopt(long)]
+    pub private: bool,
+
+    /// Encoding of the exported key ("pem" or "der").
+    #[structopt(long, default_value = "pem", parse(try_from_str=try_key_format_from_str))]
+    pub format: KeyFormat,
+
+    /// File to write the key to (standard output if not given).
+    #[structopt(long)]
+    pub out: Option<PathBuf>,
+
+    /// Overwrite the file given with `--out` if it already exists.
+    #[structopt(long, requires = "out")]
+    pub force: bool,
+}
+
+/// Write an exported key to a file, only readable by its owner if the key is private.
+fn write_key_file(path: &Path, bytes: &[u8], private: bool, force: bool) -> Result<()> {
+    let mut file = OpenOptions::new();
+    file.write(true);
+    if force {
+        file.create(true).truncate(true);
+    } else {
+        file.create_new(true);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        if private {
+            file.mode(0o600);
+        }
+    }
+
+    let written = file.open(path).and_then(|mut f| {
+        // The mode only applies to new files, so a file that is overwritten is restricted as well.
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            if private {
+                f.set_permissions(fs::Permissions::from_mode(0o600))?;
+            }
+        }
+        f.write_all(bytes)
+    });
+    match written {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => Err(Error::KeyFileExists(path.display().to_string())),
+        Err(_) => Err(Error::CannotWriteKey(path.display().to_string())),
+    }
+}
+
+#[async_trait]
+impl Command for ExportKeyCommand {
+    async fn run(&self, _ctx: &Context) -> Result<()> {
+        let bytes = Keystore::open()?.export(&self.alias, self.private, self.format)?;
+
+        match &self.out {
+            Some(path) => write_key_file(path, &bytes, self.private, self.force),
+            None => match io::stdout().write_all(&bytes) {
+                Ok(_) => Ok(()),
+                Err(_) => Err(Error::CannotWriteKey(String::from("stdout"))),
+            },
+        }
+    }
+}
+
+/// `keys list` subcommand that shows all keys in the keystore.
+#[derive(structopt::StructOpt)]
+pub struct ListKeysCommand {}
+
+#[async_trait]
+impl Command for ListKeysCommand {
+    async fn run(&self, ctx: &Context) -> Result<()> {
+        let keys = Keystore::open()?.list()?;
+
+        ctx.output.print(&keys)
+    }
+}
+
+/// `keys delete` subcommand that removes a key from the keystore.
+#[derive(structopt::StructOpt)]
+pub struct DeleteKeyCommand {
+    /// Alias of the key to delete.
+    #[structopt(parse(try_from_str=try_alias_from_str))]
+    pub alias: String,
+}
+
+#[async_trait]
+impl Command for DeleteKeyCommand {
+    async fn run(&self, ctx: &Context) -> Result<()> {
+        let key = Keystore::open()?.remove(&self.alias)?;
+
+        ctx.output.print(&key)
+    }
+}
+
+/// `keys` subcommand that manages the RSA keys used for encryption.
+#[derive(structopt::StructOpt)]
+pub enum KeysCommand {
+    /// Generate a new RSA keypair.
+    Generate(GenerateKeyCommand),
+
+    /// Import a PEM- or DER-encoded RSA key.
+    Import(ImportKeyCommand),
+
+    /// Export a key as PEM or DER.
+    Export(ExportKeyCommand),
+
+    /// List the keys in the keystore.
+    List(ListKeysCommand),
+
+    /// Delete a key from the keystore.
+    Delete(DeleteKeyCommand),
+}
+
+#[async_trait]
+impl Command for KeysCommand {
+    async fn run(&self, ctx: &Context) -> Result<()> {
+        match self {
+            Self::Generate(c) => c.run(ctx).await,
+            Self::Import(c) => c.run(ctx).await,
+            Self::Export(c) => c.run(ctx).await,
+            Self::List(c) => c.run(ctx).await,
+            Self::Delete(c) => c.run(ctx).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use structopt::StructOpt;
+
+    use super::*;
+
+    #[test]
+    fn test_alias_outside_keystore_is_rejected() {
+        assert!(ExportKeyCommand::from_iter_safe(&["export", "../../something"]).is_err());
+        assert!(DeleteKeyCommand::from_iter_safe(&["delete", "../../something"]).is_err());
+        assert_eq!(
+            "alice",
+            DeleteKeyCommand::from_iter_safe(&["delete", "alice"]).unwrap().alias
+        );
+    }
+
+    #[test]
+    fn test_write_key_file() {
+        let path = env::temp_dir().join(format!("tio-export-{}.pem", hex::encode(rand::random::<[u8; 8]>())));
+        write_key_file(&path, b"first", true, false).unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(0o600, fs::metadata(&path).unwrap().permissions().mode() & 0o777);
+        }
+
+        assert_eq!(
+            Error::KeyFileExists(path.display().to_string()),
+            write_key_file(&path, b"second", true, false).unwrap_err()
+        );
+        assert_eq!(b"first".to_vec(), fs::read(&path).unwrap());
+        write_key_file(&path, b"second", true, true).unwrap();
+        assert_eq!(b"second".to_vec(), fs::read(&path).unwrap());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,131 @@
+use async_trait::async_trait;
+
+use crate::{
+    cli::{Command, Context},
+    error::{Error, Result},
+    iota::{client::ClientArgs, find_milestone, find_milestones},
+};
+
+/// A milestone given by its index, or the latest one confirmed by the node.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MilestoneArg {
+    Index(u32),
+    Latest,
+}
+
+fn try_milestone_from_str(arg: &str) -> Result<MilestoneArg> {
+    if arg == "latest" {
+        return Ok(MilestoneArg::Latest);
+    }
+
+    match arg.parse::<u32>() {
+        Ok(i) => Ok(MilestoneArg::Index(i)),
+        Err(_) => Err(Error::MilestoneInvalid(arg.to_string())),
+    }
+}
+
+/// An inclusive range of milestone indexes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MilestoneRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+/// The maximum number of milestones that a range may span.
+pub const MAX_MILESTONE_RANGE: u32 = 1000;
+
+fn try_milestone_range_from_str(arg: &str) -> Result<MilestoneRange> {
+    let (start, end) = match arg.split_once("..") {
+        Some(bounds) => bounds,
+        None => return Err(Error::MilestoneRangeInvalid(arg.to_string())),
+    };
+
+    let (start, end) = match (start.parse::<u32>(), end.parse::<u32>()) {
+        (Ok(start), Ok(end)) if start <= end => (start, end),
+        _ => return Err(Error::MilestoneRangeInvalid(arg.to_string())),
+    };
+
+    match u64::from(end - start) + 1 {
+        n if n > u64::from(MAX_MILESTONE_RANGE) => Err(Error::MilestoneRangeTooLarge(n, MAX_MILESTONE_RANGE)),
+        _ => Ok(MilestoneRange { start, end }),
+    }
+}
+
+/// Arguments for the `milestone` subcommand.
+#[derive(Debug, structopt::StructOpt)]
+pub struct MilestoneArgs {
+    /// Index of the milestone to show, or "latest".
+    #[structopt(required_unless = "range", parse(try_from_str=try_milestone_from_str))]
+    pub milestone: Option<MilestoneArg>,
+
+    /// Inclusive range of milestone indexes to show instead (e.g. "100..110").
+    #[structopt(long, conflicts_with = "milestone", parse(try_from_str=try_milestone_range_from_str))]
+    pub range: Option<MilestoneRange>,
+}
+
+/// `milestone` subcommand that shows milestones and the ledger changes they confirmed.
+#[derive(structopt::StructOpt)]
+pub struct MilestoneCommand {
+    #[structopt(flatten)]
+    pub milestone: MilestoneArgs,
+
+    #[structopt(flatten)]
+    pub client: ClientArgs,
+}
+
+#[async_trait]
+impl Command for MilestoneCommand {
+    async fn run(&self, ctx: &Context) -> Result<()> {
+        let client = self.client.unpack_options(ctx.profile.as_ref(), &ctx.networks)?;
+
+        if let Some(range) = self.milestone.range {
+            let milestones = find_milestones(range.start, range.end, &client).await?;
+
+            return ctx.output.print(&milestones);
+        }
+
+        let index = match self.milestone.milestone {
+            Some(MilestoneArg::Index(i)) => Some(i),
+            _ => None,
+        };
+        let milestone = find_milestone(index, &client).await?;
+
+        ctx.output.print(&milestone)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_milestone_from_str() {
+        assert_eq!(MilestoneArg::Latest, try_milestone_from_str("latest").unwrap());
+        assert_eq!(MilestoneArg::Index(42), try_milestone_from_str("42").unwrap());
+        assert_eq!(
+            Error::MilestoneInvalid(String::from("first")),
+            try_milestone_from_str("first").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_try_milestone_range_from_str() {
+        assert_eq!(
+            MilestoneRange { start: 10, end: 12 },
+            try_milestone_range_from_str("10..12").unwrap()
+        );
+        assert_eq!(
+            Error::MilestoneRangeInvalid(String::from("12..10")),
+            try_milestone_range_from_str("12..10").unwrap_err()
+        );
+        assert_eq!(
+            Error::MilestoneRangeInvalid(String::from("10")),
+            try_milestone_range_from_str("10").unwrap_err()
+        );
+        assert!(try_milestone_range_from_str("1..1000").is_ok());
+        assert_eq!(
+            Error::MilestoneRangeTooLarge(2000000, MAX_MILESTONE_RANGE),
+            try_milestone_range_from_str("1..2000000").unwrap_err()
+        );
+    }
+}
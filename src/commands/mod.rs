@@ -1,8 +1,38 @@
+pub mod address;
+pub use address::AddressCommand;
+
 pub mod broadcast;
 pub use broadcast::BroadcastCommand;
 
 pub mod info;
 pub use info::InfoCommand;
 
+pub mod keys;
+pub use keys::KeysCommand;
+
+pub mod milestone;
+pub use milestone::MilestoneCommand;
+
+pub mod monitor;
+pub use monitor::MonitorCommand;
+
+pub mod output;
+pub use output::OutputCommand;
+
+pub mod pow;
+pub use pow::PowCommand;
+
+pub mod promote;
+pub use promote::PromoteCommand;
+
+pub mod reattach;
+pub use reattach::ReattachCommand;
+
 pub mod search;
 pub use search::SearchCommand;
+
+pub mod spam;
+pub use spam::SpamCommand;
+
+pub mod subscribe;
+pub use subscribe::SubscribeCommand;
@@ -0,0 +1,79 @@
+use async_trait::async_trait;
+use iota_client::bee_message::address::Address;
+
+use crate::{
+    cli::{Command, Context},
+    error::{Error, Result},
+    iota::{client::ClientArgs, monitor_address},
+    output::OutputFormat,
+};
+
+pub(crate) fn try_address_from_str(arg: &str) -> Result<String> {
+    match Address::try_from_bech32(arg) {
+        Ok(_) => Ok(arg.to_string()),
+        Err(_) => Err(Error::AddressInvalid(arg.to_string())),
+    }
+}
+
+/// Arguments for the `monitor` subcommand.
+#[derive(Debug, structopt::StructOpt)]
+pub struct MonitorArgs {
+    /// Bech32-encoded address to watch (e.g. "iota1..." or "atoi1...").
+    #[structopt(parse(try_from_str=try_address_from_str))]
+    pub address: String,
+
+    /// Number of seconds to wait between polls of the node.
+    #[structopt(long, default_value = "10")]
+    pub interval: u64,
+}
+
+/// `monitor` subcommand that watches an address for activity in the IOTA ledger.
+#[derive(structopt::StructOpt)]
+pub struct MonitorCommand {
+    #[structopt(flatten)]
+    pub monitor: MonitorArgs,
+
+    #[structopt(flatten)]
+    pub client: ClientArgs,
+}
+
+#[async_trait]
+impl Command for MonitorCommand {
+    async fn run(&self, ctx: &Context) -> Result<()> {
+        let client = self.client.unpack_options(ctx.profile.as_ref(), &ctx.networks)?;
+
+        if ctx.output == OutputFormat::Text {
+            println!(
+                "--- Address Monitor ---\n\
+                Address: {}\n\
+                Interval: {}s\n\
+                (press Ctrl+C to stop)\n",
+                self.monitor.address, self.monitor.interval,
+            );
+        }
+
+        monitor_address(&self.monitor.address, self.monitor.interval, &client, |event| {
+            if let Err(e) = ctx.output.print_event(&event) {
+                eprintln!("{}", e);
+            }
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_address_from_str() {
+        let bad_address = "iota1thisisnotanaddress";
+        let good_address = "iota1qqqnmpwza8eqgak6r9m24rykyme50cj7nm9snhkey9zfe3ftjnc9j3uh6jp";
+
+        assert_eq!(
+            Error::AddressInvalid(String::from(bad_address)),
+            try_address_from_str(bad_address).unwrap_err()
+        );
+        assert_eq!(good_address, try_address_from_str(good_address).unwrap());
+    }
+}
@@ -0,0 +1,61 @@
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use iota_client::bee_message::input::UtxoInput;
+
+use crate::{
+    cli::{Command, Context},
+    error::{Error, Result},
+    iota::{client::ClientArgs, find_output},
+};
+
+fn try_output_id_from_str(arg: &str) -> Result<String> {
+    match UtxoInput::from_str(arg) {
+        Ok(_) => Ok(arg.to_string()),
+        Err(_) => Err(Error::OutputIdInvalid(arg.to_string())),
+    }
+}
+
+/// Arguments for the `output` subcommand.
+#[derive(Debug, structopt::StructOpt)]
+pub struct OutputArgs {
+    /// Output ID to look up (hex-encoded transaction ID followed by the output index).
+    #[structopt(parse(try_from_str=try_output_id_from_str))]
+    pub output_id: String,
+}
+
+/// `output` subcommand that shows an output in the IOTA ledger.
+#[derive(structopt::StructOpt)]
+pub struct OutputCommand {
+    #[structopt(flatten)]
+    pub output: OutputArgs,
+
+    #[structopt(flatten)]
+    pub client: ClientArgs,
+}
+
+#[async_trait]
+impl Command for OutputCommand {
+    async fn run(&self, ctx: &Context) -> Result<()> {
+        let client = self.client.unpack_options(ctx.profile.as_ref(), &ctx.networks)?;
+
+        let output = find_output(&self.output.output_id, &client).await?;
+
+        ctx.output.print(&output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_output_id_from_str() {
+        let id = format!("{}0000", "ab".repeat(32));
+        assert_eq!(id, try_output_id_from_str(&id).unwrap());
+        assert_eq!(
+            Error::OutputIdInvalid(String::from("0xab")),
+            try_output_id_from_str("0xab").unwrap_err()
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use async_trait::async_trait;
+
+use crate::{
+    cli::{Command, Context},
+    error::Result,
+    iota::{
+        client::ClientArgs,
+        pow::{
+            bench_pow, get_min_pow_score, try_pow_samples_from_str, try_pow_score_from_str, try_pow_workers_from_str,
+        },
+    },
+    output::OutputFormat,
+};
+
+/// `pow bench` subcommand that measures the local proof of work hash rate.
+#[derive(structopt::StructOpt)]
+pub struct BenchPowCommand {
+    /// Number of threads to do the proof of work with (defaults to the number of CPUs).
+    #[structopt(long, parse(try_from_str=try_pow_workers_from_str))]
+    pub workers: Option<usize>,
+
+    /// Number of nonces to mine for the measurement.
+    #[structopt(long, default_value = "5", parse(try_from_str=try_pow_samples_from_str))]
+    pub samples: usize,
+
+    /// Size in bytes of the message to estimate the proof of work for.
+    #[structopt(long, default_value = "256")]
+    pub size: usize,
+
+    /// Minimum proof of work score to estimate for, instead of asking the node for it.
+    #[structopt(long, parse(try_from_str=try_pow_score_from_str))]
+    pub score: Option<f64>,
+
+    #[structopt(flatten)]
+    pub client: ClientArgs,
+}
+
+#[async_trait]
+impl Command for BenchPowCommand {
+    async fn run(&self, ctx: &Context) -> Result<()> {
+        let score = match self.score {
+            Some(s) => s,
+            None => {
+                let client = self.client.unpack_options(ctx.profile.as_ref(), &ctx.networks)?;
+                get_min_pow_score(&client).await?
+            }
+        };
+
+        if ctx.output == OutputFormat::Text {
+            eprintln!("Mining {} nonce(s) to measure the hash rate...", self.samples);
+        }
+        let bench = bench_pow(self.workers, self.samples, score, self.size).await?;
+
+        ctx.output.print(&bench)
+    }
+}
+
+/// `pow` subcommand that inspects the proof of work done for messages.
+#[derive(structopt::StructOpt)]
+pub enum PowCommand {
+    /// Measure the local hash rate and estimate the proof of work time of a message.
+    Bench(BenchPowCommand),
+}
+
+#[async_trait]
+impl Command for PowCommand {
+    async fn run(&self, ctx: &Context) -> Result<()> {
+        match self {
+            Self::Bench(c) => c.run(ctx).await,
+        }
+    }
+}
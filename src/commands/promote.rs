@@ -0,0 +1,108 @@
+use std::{fs, path::PathBuf};
+
+use async_trait::async_trait;
+
+use crate::{
+    cli::{Command, Context},
+    commands::search::try_hash_bytes_from_str,
+    error::{Error, Result},
+    iota::{
+        client::ClientArgs,
+        confirm::{remedy_messages, RemedyAction},
+        views::RemedyOutcome,
+    },
+};
+
+/// Arguments for the `promote` and `reattach` subcommands.
+#[derive(Debug, structopt::StructOpt)]
+pub struct StuckMessageArgs {
+    /// Hashes of the messages (each must be hexadecimal string of exactly 32 bytes).
+    #[structopt(required_unless = "file", parse(try_from_str=try_hash_bytes_from_str))]
+    pub ids: Vec<[u8; 32]>,
+
+    /// File to read additional message IDs from, one per line ("#" starts a comment).
+    #[structopt(long)]
+    pub file: Option<PathBuf>,
+
+    /// Perform the action even if the node does not suggest it.
+    #[structopt(long)]
+    pub force: bool,
+}
+
+impl StuckMessageArgs {
+    /// Collect the message IDs given as arguments and those in the file, if any.
+    pub fn unpack_ids(&self) -> Result<Vec<[u8; 32]>> {
+        let mut ids = self.ids.clone();
+
+        if let Some(path) = &self.file {
+            let contents = match fs::read_to_string(path) {
+                Ok(c) => c,
+                Err(_) => return Err(Error::CannotReadData(path.display().to_string())),
+            };
+            for line in contents.lines().map(str::trim) {
+                if !line.is_empty() && !line.starts_with('#') {
+                    ids.push(try_hash_bytes_from_str(line)?);
+                }
+            }
+        }
+
+        Ok(ids)
+    }
+
+    pub(crate) async fn run(&self, action: RemedyAction, client: &ClientArgs, ctx: &Context) -> Result<()> {
+        let ids = self.unpack_ids()?;
+        let client = client.unpack_options(ctx.profile.as_ref(), &ctx.networks)?;
+
+        let results = remedy_messages(&ids, action, self.force, &client).await?;
+        ctx.output.print(&results)?;
+
+        match results.iter().filter(|r| r.outcome == RemedyOutcome::Failed).count() {
+            0 => Ok(()),
+            n => Err(Error::RemedyIncomplete(n)),
+        }
+    }
+}
+
+/// `promote` subcommand that promotes messages which are not yet confirmed.
+#[derive(structopt::StructOpt)]
+pub struct PromoteCommand {
+    #[structopt(flatten)]
+    pub messages: StuckMessageArgs,
+
+    #[structopt(flatten)]
+    pub client: ClientArgs,
+}
+
+#[async_trait]
+impl Command for PromoteCommand {
+    async fn run(&self, ctx: &Context) -> Result<()> {
+        self.messages.run(RemedyAction::Promote, &self.client, ctx).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unpack_ids() {
+        let id = "9d097abc7abef5c51f31a33655f3f15e100d4634f930a07ebbcfe3f0ab98b620";
+        let path = std::env::temp_dir().join(format!("tio-ids-{}", hex::encode(rand::random::<[u8; 8]>())));
+        fs::write(&path, format!("# stuck messages\n{}\n\n  {}  \n", id, id)).unwrap();
+
+        let args = StuckMessageArgs {
+            ids: vec![try_hash_bytes_from_str(id).unwrap()],
+            file: Some(path.clone()),
+            force: false,
+        };
+        assert_eq!(3, args.unpack_ids().unwrap().len());
+
+        fs::write(&path, "not an id\n").unwrap();
+        assert_eq!(
+            Error::MessageHashInvalid(String::from("not an id")),
+            args.unpack_ids().unwrap_err()
+        );
+
+        fs::remove_file(path).unwrap();
+    }
+}
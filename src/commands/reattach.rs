@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+
+use crate::{
+    cli::{Command, Context},
+    commands::promote::StuckMessageArgs,
+    error::Result,
+    iota::{client::ClientArgs, confirm::RemedyAction},
+};
+
+/// `reattach` subcommand that reattaches messages which are not yet confirmed.
+#[derive(structopt::StructOpt)]
+pub struct ReattachCommand {
+    #[structopt(flatten)]
+    pub messages: StuckMessageArgs,
+
+    #[structopt(flatten)]
+    pub client: ClientArgs,
+}
+
+#[async_trait]
+impl Command for ReattachCommand {
+    async fn run(&self, ctx: &Context) -> Result<()> {
+        self.messages.run(RemedyAction::Reattach, &self.client, ctx).await
+    }
+}
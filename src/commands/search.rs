@@ -1,43 +1,114 @@
-use std::convert::TryInto;
+use std::{convert::TryInto, fs, path::PathBuf};
 
 use async_trait::async_trait;
 use hex::decode;
 
 use crate::{
-    cli::Command,
+    cli::{Command, Context},
+    commands::broadcast::{try_data_encoding_from_str, DataEncoding, MAX_INDEX_BYTES},
     error::{Error, Result},
-    iota::{client::ClientArgs, find_message},
+    iota::{
+        client::{ClientArgs, ClientOptions},
+        find_message, find_messages_by_index,
+        quorum::{quorum_read, QuorumArgs},
+        views::DataFormat,
+    },
+    keys::load_private_key,
 };
 
-fn try_hash_from_str(arg: &str) -> Result<String> {
-    let bytes: Vec<u8> = match decode(arg) {
+/// Parse a message ID into its raw bytes.
+pub(crate) fn try_hash_bytes_from_str(arg: &str) -> Result<[u8; 32]> {
+    let bytes = match decode(arg) {
         Ok(b) => b,
         Err(_) => return Err(Error::MessageHashInvalid(arg.to_string())),
     };
 
-    match bytes.len() {
-        32 => Ok(arg.to_string()),
-        _ => Err(Error::MessageHashInvalid(arg.to_string())),
+    match bytes.try_into() {
+        Ok(arr) => Ok(arr),
+        Err(_) => Err(Error::MessageHashInvalid(arg.to_string())),
     }
 }
 
+fn try_data_format_from_str(arg: &str) -> Result<DataFormat> {
+    match arg {
+        "auto" => Ok(DataFormat::Auto),
+        "utf8" | "utf-8" => Ok(DataFormat::Utf8),
+        "hex" => Ok(DataFormat::Hex),
+        "base64" => Ok(DataFormat::Base64),
+        "hexdump" => Ok(DataFormat::Hexdump),
+        "json" => Ok(DataFormat::Json),
+        "cbor" => Ok(DataFormat::Cbor),
+        "msgpack" | "messagepack" => Ok(DataFormat::MessagePack),
+        _ => Err(Error::DataFormatInvalid(arg.to_string())),
+    }
+}
+
+pub(crate) fn try_hash_from_str(arg: &str) -> Result<String> {
+    try_hash_bytes_from_str(arg)?;
+
+    Ok(arg.to_string())
+}
+
 /// Arguments for the `search` command.
 #[derive(Debug, structopt::StructOpt)]
 pub struct SearchArgs {
     /// Hash of a message (must be hexadecimal string of exactly 32 bytes).
-    #[structopt(parse(try_from_str=try_hash_from_str))]
-    pub id: String,
+    #[structopt(required_unless = "index", parse(try_from_str=try_hash_from_str))]
+    pub id: Option<String>,
+
+    /// Indexation key to list the messages of instead of searching by ID.
+    #[structopt(long, conflicts_with = "id")]
+    pub index: Option<String>,
+
+    /// Encoding of the given indexation key ("utf8", "hex" or "base64").
+    #[structopt(long, default_value = "utf8", parse(try_from_str=try_data_encoding_from_str))]
+    pub index_encoding: DataEncoding,
+
+    /// Maximum number of messages to list for an index.
+    #[structopt(long)]
+    pub limit: Option<usize>,
+
+    /// Fetch and show every message found for an index rather than only their IDs.
+    #[structopt(long)]
+    pub fetch: bool,
+
+    /// Fetch the outputs spent by a transaction to show their address, amount and originating message.
+    #[structopt(long)]
+    pub resolve_inputs: bool,
+
+    /// Alias (or key file) of the RSA private key to decrypt encrypted data with.
+    #[structopt(long, value_name = "key")]
+    pub decrypt_with: Option<String>,
+
+    /// Format to show message data in ("auto", "utf8", "hex", "base64", "hexdump", "json", "cbor" or "msgpack").
+    #[structopt(long, default_value = "auto", parse(try_from_str=try_data_format_from_str))]
+    pub data_format: DataFormat,
+
+    /// Format to show indexation keys in ("auto", "utf8", "hex", "base64", "hexdump", "json", "cbor" or "msgpack").
+    #[structopt(long, default_value = "auto", parse(try_from_str=try_data_format_from_str))]
+    pub index_format: DataFormat,
+
+    /// File to write the exact bytes of the message data to.
+    #[structopt(long, value_name = "file", conflicts_with = "index", parse(from_os_str))]
+    pub raw_out: Option<PathBuf>,
 }
 
 impl SearchArgs {
-    pub fn unpack_hash(&self) -> [u8; 32] {
-        let boxed = decode(&self.id).unwrap().into_boxed_slice();
-        let boxed_arr: Box<[u8; 32]> = match boxed.try_into() {
-            Ok(ba) => ba,
-            Err(_) => panic!("{:?}", Error::MessageHashInvalid(self.id.clone())),
+    pub fn unpack_hash(&self) -> Result<[u8; 32]> {
+        try_hash_bytes_from_str(self.id.as_deref().unwrap_or_default())
+    }
+
+    /// Decode the indexation key to search by, if one was given.
+    pub fn unpack_index(&self) -> Result<Option<Vec<u8>>> {
+        let index = match &self.index {
+            Some(i) => self.index_encoding.decode(i.as_bytes())?,
+            None => return Ok(None),
         };
 
-        *boxed_arr
+        match index.len() {
+            s if s > 0 && s < MAX_INDEX_BYTES => Ok(Some(index)),
+            s => Err(Error::MessageDataIndexTooLarge(s)),
+        }
     }
 }
 
@@ -47,17 +118,71 @@ pub struct SearchCommand {
     #[structopt(flatten)]
     pub search: SearchArgs,
 
+    #[structopt(flatten)]
+    pub quorum: QuorumArgs,
+
     #[structopt(flatten)]
     pub client: ClientArgs,
 }
 
 #[async_trait]
 impl Command for SearchCommand {
-    async fn run(&self) -> Result<()> {
-        let id: &[u8; 32] = &self.search.unpack_hash();
-        let node_url = self.client.unpack_url();
+    async fn run(&self, ctx: &Context) -> Result<()> {
+        let client = self.client.unpack_options(ctx.profile.as_ref(), &ctx.networks)?;
+
+        if let Some(index) = self.search.unpack_index()? {
+            let args = &self.search;
+            let index = &index;
+            let read = |c: ClientOptions| async move {
+                find_messages_by_index(index, args.limit, args.fetch, args.resolve_inputs, &c).await
+            };
+            let mut found = match self.quorum.quorum {
+                Some(q) => quorum_read(&client, q, read).await?,
+                None => read(client).await?,
+            };
+            if let (Some(key), Some(messages)) = (&self.search.decrypt_with, found.messages.as_mut()) {
+                let private_key = load_private_key(key)?;
+                for message in messages {
+                    if let Err(e) = message.payload.decrypt_with(&private_key) {
+                        eprintln!("warning: unable to decrypt message {}: {}", message.id, e);
+                    }
+                }
+            }
+            found.index.format = args.index_format;
+            for message in found.messages.iter_mut().flatten() {
+                message.payload.set_data_formats(args.index_format, args.data_format);
+            }
+
+            return ctx.output.print(&found);
+        }
+
+        let id: &[u8; 32] = &self.search.unpack_hash()?;
+
+        let resolve_inputs = self.search.resolve_inputs;
+        let read = |c: ClientOptions| async move { find_message(id, resolve_inputs, &c).await };
+        let mut message = match self.quorum.quorum {
+            Some(q) => quorum_read(&client, q, read).await?,
+            None => read(client).await?,
+        };
+        if let Some(key) = &self.search.decrypt_with {
+            let private_key = load_private_key(key)?;
+            message.payload.decrypt_with(&private_key)?;
+        }
+        message
+            .payload
+            .set_data_formats(self.search.index_format, self.search.data_format);
+
+        if let Some(path) = &self.search.raw_out {
+            let data = match message.payload.indexation_data() {
+                Some(d) => d,
+                None => return Err(Error::MessageEmpty),
+            };
+            if fs::write(path, data).is_err() {
+                return Err(Error::CannotWriteData(path.display().to_string()));
+            }
+        }
 
-        Ok(find_message(id, node_url).await)
+        ctx.output.print(&message)
     }
 }
 
@@ -78,4 +203,34 @@ mod tests {
         assert_eq!(error_fn(half_id), try_hash_from_str(half_id).unwrap_err());
         assert_eq!(good_id, try_hash_from_str(good_id).unwrap());
     }
+
+    #[test]
+    fn test_try_data_format_from_str() {
+        assert_eq!(DataFormat::Hexdump, try_data_format_from_str("hexdump").unwrap());
+        assert_eq!(DataFormat::Utf8, try_data_format_from_str("utf-8").unwrap());
+        assert_eq!(
+            Error::DataFormatInvalid(String::from("binary")),
+            try_data_format_from_str("binary").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_unpack_index() {
+        let mut args = SearchArgs {
+            id: None,
+            index: Some(String::from("74696f2d636c69")),
+            index_encoding: DataEncoding::Hex,
+            limit: None,
+            fetch: false,
+            resolve_inputs: false,
+            decrypt_with: None,
+            data_format: DataFormat::Auto,
+            index_format: DataFormat::Auto,
+            raw_out: None,
+        };
+        assert_eq!(Some(b"tio-cli".to_vec()), args.unpack_index().unwrap());
+
+        args.index = Some(String::new());
+        assert_eq!(Error::MessageDataIndexTooLarge(0), args.unpack_index().unwrap_err());
+    }
 }
@@ -0,0 +1,167 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+
+use crate::{
+    cli::{Command, Context},
+    commands::broadcast::{try_data_from_str, try_data_index_from_str},
+    error::{Error, Result},
+    iota::{
+        client::ClientArgs,
+        spam::{render_template, spam_messages, SpamCallback, SpamOptions},
+    },
+    output::OutputFormat,
+};
+
+/// The number of messages sent when neither a count nor a duration is given.
+pub const DEFAULT_SPAM_COUNT: u64 = 10;
+
+fn try_index_template_from_str(arg: &str) -> Result<String> {
+    try_data_index_from_str(&render_template(arg, u64::MAX))?;
+
+    Ok(arg.to_string())
+}
+
+fn try_data_template_from_str(arg: &str) -> Result<String> {
+    try_data_from_str(&render_template(arg, u64::MAX))?;
+
+    Ok(arg.to_string())
+}
+
+/// The lowest target rate, in messages per second.
+const MIN_RATE: f64 = 1e-6;
+
+/// The highest target rate, in messages per second.
+const MAX_RATE: f64 = 1e6;
+
+fn try_rate_from_str(arg: &str) -> Result<f64> {
+    // Rates outside of these bounds would give a period between messages that is zero or too long to represent.
+    match arg.parse::<f64>() {
+        Ok(r) if (MIN_RATE..=MAX_RATE).contains(&r) => Ok(r),
+        _ => Err(Error::SpamRateInvalid(arg.to_string())),
+    }
+}
+
+fn try_concurrency_from_str(arg: &str) -> Result<usize> {
+    match arg.parse::<usize>() {
+        Ok(c) if c > 0 => Ok(c),
+        _ => Err(Error::SpamConcurrencyInvalid(arg.to_string())),
+    }
+}
+
+/// Arguments for the `spam` subcommand.
+#[derive(Debug, structopt::StructOpt)]
+pub struct SpamArgs {
+    /// Number of messages to send (defaults to 10 when no duration is given).
+    #[structopt(short, long, conflicts_with = "duration")]
+    pub count: Option<u64>,
+
+    /// Number of seconds to keep sending messages for.
+    #[structopt(short, long)]
+    pub duration: Option<u64>,
+
+    /// Target number of messages per second (unlimited if not given).
+    #[structopt(short, long, parse(try_from_str=try_rate_from_str))]
+    pub rate: Option<f64>,
+
+    /// Number of messages allowed to be in flight at once.
+    #[structopt(long, default_value = "1", parse(try_from_str=try_concurrency_from_str))]
+    pub concurrency: usize,
+
+    /// Template for the indexation key ("{seq}", "{rand}" and "{timestamp}" are replaced).
+    #[structopt(long, default_value = "tio-spam", parse(try_from_str=try_index_template_from_str))]
+    pub index: String,
+
+    /// Template for the data ("{seq}", "{rand}" and "{timestamp}" are replaced).
+    #[structopt(
+        long,
+        default_value = "tio-spam-message #{seq}",
+        parse(try_from_str=try_data_template_from_str)
+    )]
+    pub data: String,
+}
+
+impl SpamArgs {
+    pub fn unpack_options(&self) -> SpamOptions {
+        let count = match (self.count, self.duration) {
+            (None, None) => Some(DEFAULT_SPAM_COUNT),
+            (c, _) => c,
+        };
+
+        SpamOptions {
+            count,
+            duration: self.duration.map(Duration::from_secs),
+            rate: self.rate,
+            concurrency: self.concurrency,
+            index: self.index.clone(),
+            data: self.data.clone(),
+        }
+    }
+}
+
+/// `spam` subcommand that repeatedly broadcasts messages to the IOTA Tangle.
+#[derive(structopt::StructOpt)]
+pub struct SpamCommand {
+    #[structopt(flatten)]
+    pub spam: SpamArgs,
+
+    #[structopt(flatten)]
+    pub client: ClientArgs,
+}
+
+#[async_trait]
+impl Command for SpamCommand {
+    async fn run(&self, ctx: &Context) -> Result<()> {
+        let options = self.spam.unpack_options();
+        let client = self.client.unpack_options(ctx.profile.as_ref(), &ctx.networks)?;
+
+        let format = ctx.output;
+        let on_sent: SpamCallback = Arc::new(move |seq, result, latency| match result {
+            Ok(id) if format == OutputFormat::Text => println!("[{}] {} ({} ms)", seq, id, latency.as_millis()),
+            Ok(_) => (),
+            Err(e) => eprintln!("[{}] {}", seq, e),
+        });
+
+        let report = spam_messages(options, &client, on_sent).await?;
+
+        ctx.output.print(&report.summary())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_rate_from_str() {
+        assert_eq!(2.5, try_rate_from_str("2.5").unwrap());
+        assert_eq!(
+            Error::SpamRateInvalid(String::from("0")),
+            try_rate_from_str("0").unwrap_err()
+        );
+        assert_eq!(
+            Error::SpamRateInvalid(String::from("fast")),
+            try_rate_from_str("fast").unwrap_err()
+        );
+        assert_eq!(MAX_RATE, try_rate_from_str("1e6").unwrap());
+        assert_eq!(MIN_RATE, try_rate_from_str("1e-6").unwrap());
+        assert_eq!(
+            Error::SpamRateInvalid(String::from("2e9")),
+            try_rate_from_str("2e9").unwrap_err()
+        );
+        assert_eq!(
+            Error::SpamRateInvalid(String::from("1e-300")),
+            try_rate_from_str("1e-300").unwrap_err()
+        );
+        assert!(try_rate_from_str("inf").is_err());
+        assert!(try_rate_from_str("NaN").is_err());
+    }
+
+    #[test]
+    fn test_try_index_template_from_str() {
+        let long_index = "a".repeat(60) + "{seq}";
+
+        assert_eq!("tio-{seq}", try_index_template_from_str("tio-{seq}").unwrap());
+        assert!(try_index_template_from_str(&long_index).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+use async_trait::async_trait;
+
+use crate::{
+    cli::{Command, Context},
+    commands::{
+        broadcast::{try_data_index_from_str, MAX_INDEX_BYTES},
+        monitor::try_address_from_str,
+        search::try_hash_from_str,
+    },
+    error::{Error, Result},
+    iota::{
+        client::ClientArgs,
+        subscribe::{subscribe, SubscriptionTopic},
+    },
+    output::OutputFormat,
+};
+
+/// Arguments for the `subscribe` subcommand.
+#[derive(Debug, structopt::StructOpt)]
+pub struct SubscribeArgs {
+    /// Subscribe to every new milestone.
+    #[structopt(long)]
+    pub milestones: bool,
+
+    /// Subscribe to every milestone once it is confirmed.
+    #[structopt(long)]
+    pub confirmed_milestones: bool,
+
+    /// Subscribe to every new message.
+    #[structopt(long)]
+    pub messages: bool,
+
+    /// Subscribe to new messages with an indexation key (may be repeated).
+    #[structopt(long, number_of_values = 1, parse(try_from_str=try_data_index_from_str))]
+    pub index: Vec<String>,
+
+    /// Subscribe to changes to the metadata of a message given its ID (may be repeated).
+    #[structopt(long, value_name = "id", number_of_values = 1, parse(try_from_str=try_hash_from_str))]
+    pub metadata: Vec<String>,
+
+    /// Subscribe to outputs created on or spent from a Bech32-encoded address (may be repeated).
+    #[structopt(long, number_of_values = 1, parse(try_from_str=try_address_from_str))]
+    pub address: Vec<String>,
+}
+
+impl SubscribeArgs {
+    /// Collect every selected topic, of which there must be at least one.
+    pub fn unpack_topics(&self) -> Result<Vec<SubscriptionTopic>> {
+        let mut topics = Vec::new();
+        if self.milestones {
+            topics.push(SubscriptionTopic::LatestMilestones);
+        }
+        if self.confirmed_milestones {
+            topics.push(SubscriptionTopic::ConfirmedMilestones);
+        }
+        if self.messages {
+            topics.push(SubscriptionTopic::Messages);
+        }
+        for index in &self.index {
+            match index.len() {
+                s if s > 0 && s < MAX_INDEX_BYTES => topics.push(SubscriptionTopic::Index(index.as_bytes().to_vec())),
+                s => return Err(Error::MessageDataIndexTooLarge(s)),
+            }
+        }
+        topics.extend(self.metadata.iter().cloned().map(SubscriptionTopic::Metadata));
+        topics.extend(self.address.iter().cloned().map(SubscriptionTopic::AddressOutputs));
+
+        if topics.is_empty() {
+            return Err(Error::SubscriptionTopicMissing);
+        }
+
+        Ok(topics)
+    }
+}
+
+/// `subscribe` subcommand that streams events from a node as they happen.
+#[derive(structopt::StructOpt)]
+pub struct SubscribeCommand {
+    #[structopt(flatten)]
+    pub subscribe: SubscribeArgs,
+
+    #[structopt(flatten)]
+    pub client: ClientArgs,
+}
+
+#[async_trait]
+impl Command for SubscribeCommand {
+    async fn run(&self, ctx: &Context) -> Result<()> {
+        let topics = self.subscribe.unpack_topics()?;
+        let client = self.client.unpack_options(ctx.profile.as_ref(), &ctx.networks)?;
+
+        if ctx.output == OutputFormat::Text {
+            let paths: Vec<String> = topics.iter().map(SubscriptionTopic::path).collect();
+            println!(
+                "--- Subscription ---\n\
+                Topic(s): {}\n\
+                (press Ctrl+C to stop)\n",
+                paths.join(", "),
+            );
+        }
+
+        subscribe(&topics, &client, |event| {
+            if let Err(e) = ctx.output.print_event(&event) {
+                eprintln!("{}", e);
+            }
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unpack_topics() {
+        let mut args = SubscribeArgs {
+            milestones: false,
+            confirmed_milestones: true,
+            messages: false,
+            index: vec![String::from("tio-cli")],
+            metadata: vec![],
+            address: vec![],
+        };
+        assert_eq!(
+            vec![
+                SubscriptionTopic::ConfirmedMilestones,
+                SubscriptionTopic::Index(b"tio-cli".to_vec())
+            ],
+            args.unpack_topics().unwrap()
+        );
+
+        args.confirmed_milestones = false;
+        args.index.clear();
+        assert_eq!(Error::SubscriptionTopicMissing, args.unpack_topics().unwrap_err());
+    }
+}
@@ -0,0 +1,180 @@
+use std::{collections::HashMap, env, fs, path::PathBuf};
+
+use serde::Deserialize;
+
+use crate::{
+    error::{Error, Result},
+    secret::Secret,
+};
+
+/// Environment variable that overrides the location of the configuration file.
+pub const CONFIG_PATH_ENV: &str = "TIO_CONFIG";
+
+/// A named set of defaults for connecting to a network and presenting output.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Profile {
+    /// IOTA Tangle network to use ("mainnet", "devnet" or a network defined in the configuration file).
+    pub network: Option<String>,
+    /// Node URLs to send API requests to, taking precedence over the network's nodes.
+    pub urls: Vec<String>,
+    /// Username for basic authentication with the node.
+    pub username: Option<String>,
+    /// Password for basic authentication with the node.
+    pub password: Option<Secret>,
+    /// JSON web token for authentication with the node.
+    pub jwt: Option<Secret>,
+    /// Whether proof of work is done locally rather than by the node.
+    pub local_pow: Option<bool>,
+    /// Number of threads used for local proof of work.
+    pub pow_workers: Option<usize>,
+    /// Number of seconds to wait for a message to be sent, including its proof of work.
+    pub pow_timeout: Option<u64>,
+    /// Format of the command output ("text" or "json").
+    pub output: Option<String>,
+}
+
+/// A user-defined network, such as a private network of nodes.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct NetworkConfig {
+    /// Network ID that the nodes must report (e.g. "private-tangle").
+    pub network_id: Option<String>,
+    /// Human-readable part of the Bech32 addresses that the nodes must report (e.g. "atoi").
+    pub bech32_hrp: Option<String>,
+    /// Node URLs of the network.
+    pub urls: Vec<String>,
+    /// Base URL of the network's explorer, to which "/message/<id>" is appended to link to a message.
+    pub explorer: Option<String>,
+    /// Whether proof of work is done locally rather than by the node.
+    pub local_pow: Option<bool>,
+}
+
+/// The contents of the `tio` configuration file.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Profile to use when none is specified.
+    pub default_profile: Option<String>,
+    /// Profiles keyed by their name.
+    pub profiles: HashMap<String, Profile>,
+    /// User-defined networks keyed by their name.
+    pub networks: HashMap<String, NetworkConfig>,
+}
+
+impl Config {
+    /// The directory in which `tio` keeps its files, which is `tio` within `$XDG_CONFIG_HOME` (or `~/.config`).
+    pub fn dir() -> Option<PathBuf> {
+        let config_dir = match env::var_os("XDG_CONFIG_HOME") {
+            Some(d) if !d.is_empty() => PathBuf::from(d),
+            _ => dirs::home_dir()?.join(".config"),
+        };
+
+        Some(config_dir.join("tio"))
+    }
+
+    /// The location of the configuration file, which is `$TIO_CONFIG` if set, otherwise `config.toml` within
+    /// [`Config::dir`].
+    pub fn path() -> Option<PathBuf> {
+        match env::var_os(CONFIG_PATH_ENV) {
+            Some(p) => Some(PathBuf::from(p)),
+            None => Some(Self::dir()?.join("config.toml")),
+        }
+    }
+
+    /// Parse a configuration from its TOML representation.
+    pub fn parse(contents: &str) -> Result<Self> {
+        match toml::from_str(contents) {
+            Ok(c) => Ok(c),
+            Err(e) => Err(Error::ConfigInvalid(e.to_string())),
+        }
+    }
+
+    /// Load the configuration file, falling back to an empty configuration if it does not exist.
+    pub fn load() -> Result<Self> {
+        let path = match Self::path() {
+            Some(p) if p.exists() => p,
+            _ => return Ok(Self::default()),
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(contents) => Self::parse(&contents),
+            Err(e) => Err(Error::ConfigInvalid(e.to_string())),
+        }
+    }
+
+    /// Find the profile with the given name, or the default profile if no name is given.
+    pub fn profile(&self, name: Option<&str>) -> Result<Option<Profile>> {
+        let name = match name.or_else(|| self.default_profile.as_deref()) {
+            Some(n) => n,
+            None => return Ok(None),
+        };
+
+        match self.profiles.get(name) {
+            Some(p) => Ok(Some(p.clone())),
+            None => Err(Error::ProfileNotFound(name.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+        default_profile = "staging"
+
+        [profiles.staging]
+        network = "devnet"
+        urls = ["https://staging.example.com"]
+        local_pow = true
+        pow_workers = 4
+        pow_timeout = 120
+        output = "json"
+
+        [profiles.production]
+        urls = ["https://node-1.example.com", "https://node-2.example.com"]
+        username = "tio"
+        password = "secret"
+
+        [networks.private]
+        network_id = "private-tangle"
+        bech32_hrp = "atoi"
+        urls = ["https://hornet.example.com"]
+        explorer = "https://explorer.example.com/private"
+    "#;
+
+    #[test]
+    fn test_config_profile() {
+        let config = Config::parse(CONFIG).unwrap();
+
+        let staging = config.profile(None).unwrap().unwrap();
+        assert_eq!(Some(String::from("devnet")), staging.network);
+        assert_eq!(Some(true), staging.local_pow);
+        assert_eq!(Some(4), staging.pow_workers);
+        assert_eq!(Some(120), staging.pow_timeout);
+
+        let production = config.profile(Some("production")).unwrap().unwrap();
+        assert_eq!(2, production.urls.len());
+        assert_eq!(Some(String::from("tio")), production.username);
+        assert_eq!(Some("secret"), production.password.as_ref().map(Secret::expose));
+
+        assert_eq!(
+            Error::ProfileNotFound(String::from("missing")),
+            config.profile(Some("missing")).unwrap_err()
+        );
+        assert_eq!(None, Config::default().profile(None).unwrap());
+        assert!(Config::parse("unknown = 1").is_err());
+    }
+
+    #[test]
+    fn test_config_networks() {
+        let config = Config::parse(CONFIG).unwrap();
+
+        let private = &config.networks["private"];
+        assert_eq!(Some(String::from("private-tangle")), private.network_id);
+        assert_eq!(Some(String::from("atoi")), private.bech32_hrp);
+        assert_eq!(vec![String::from("https://hornet.example.com")], private.urls);
+        assert_eq!(None, private.local_pow);
+    }
+}
@@ -0,0 +1,185 @@
+use aes_gcm::{
+    aead::{Aead, NewAead, Payload},
+    Aes256Gcm, Key, Nonce,
+};
+use rand::{rngs::OsRng, RngCore};
+use rsa::{PaddingScheme, PublicKey, RsaPrivateKey, RsaPublicKey};
+
+use crate::error::{Error, Result};
+
+/// Bytes that every encrypted envelope starts with.
+pub const ENVELOPE_MAGIC: &[u8; 4] = b"TIOE";
+
+/// The current version of the envelope format.
+pub const ENVELOPE_VERSION: u8 = 1;
+
+const HEADER_LEN: usize = ENVELOPE_MAGIC.len() + 2;
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+
+/// The available algorithms for encrypting data.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Algorithm {
+    /// A random AES-256-GCM key that is wrapped with RSA-OAEP (SHA-256).
+    RsaAes256Gcm,
+}
+
+impl Algorithm {
+    fn id(&self) -> u8 {
+        match self {
+            Self::RsaAes256Gcm => 1,
+        }
+    }
+
+    fn from_id(id: u8) -> Result<Self> {
+        match id {
+            1 => Ok(Self::RsaAes256Gcm),
+            _ => Err(Error::CannotDecryptData),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::RsaAes256Gcm => "rsa-aes-256-gcm",
+        }
+    }
+}
+
+impl Default for Algorithm {
+    fn default() -> Self {
+        Self::RsaAes256Gcm
+    }
+}
+
+pub(crate) fn try_algorithm_from_str(arg: &str) -> Result<Algorithm> {
+    match arg {
+        "rsa-aes-256-gcm" => Ok(Algorithm::RsaAes256Gcm),
+        _ => Err(Error::EncryptionAlgorithmInvalid(arg.to_string())),
+    }
+}
+
+fn oaep() -> PaddingScheme {
+    PaddingScheme::new_oaep::<sha2::Sha256>()
+}
+
+/// Whether the data is (or claims to be) an encrypted envelope.
+pub fn is_envelope(data: &[u8]) -> bool {
+    data.starts_with(ENVELOPE_MAGIC)
+}
+
+/// Encrypt data for the holder of the private key that belongs to the given public key.
+///
+/// The envelope is laid out as the magic bytes, version, algorithm ID, length of the wrapped key (2 bytes, big
+/// endian), wrapped key, nonce, and finally the ciphertext, whose tag also authenticates the header.
+pub fn encrypt(plaintext: &[u8], public_key: &RsaPublicKey, algorithm: Algorithm) -> Result<Vec<u8>> {
+    let mut key = [0u8; KEY_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut key);
+    OsRng.fill_bytes(&mut nonce);
+
+    let wrapped_key = match public_key.encrypt(&mut OsRng, oaep(), &key) {
+        Ok(k) => k,
+        Err(_) => return Err(Error::CannotEncryptData),
+    };
+
+    let mut envelope = Vec::with_capacity(HEADER_LEN + 2 + wrapped_key.len() + NONCE_LEN + plaintext.len() + 16);
+    envelope.extend_from_slice(ENVELOPE_MAGIC);
+    envelope.push(ENVELOPE_VERSION);
+    envelope.push(algorithm.id());
+
+    let cipher = Aes256Gcm::new(Key::from_slice(&key));
+    let payload = Payload {
+        msg: plaintext,
+        aad: &envelope[..HEADER_LEN],
+    };
+    let ciphertext = match cipher.encrypt(Nonce::from_slice(&nonce), payload) {
+        Ok(c) => c,
+        Err(_) => return Err(Error::CannotEncryptData),
+    };
+
+    envelope.extend_from_slice(&(wrapped_key.len() as u16).to_be_bytes());
+    envelope.extend_from_slice(&wrapped_key);
+    envelope.extend_from_slice(&nonce);
+    envelope.extend_from_slice(&ciphertext);
+
+    Ok(envelope)
+}
+
+/// Decrypt an envelope that was encrypted for the given private key.
+pub fn decrypt(envelope: &[u8], private_key: &RsaPrivateKey) -> Result<Vec<u8>> {
+    if !is_envelope(envelope) || envelope.len() < HEADER_LEN + 2 {
+        return Err(Error::CannotDecryptData);
+    }
+    if envelope[ENVELOPE_MAGIC.len()] != ENVELOPE_VERSION {
+        return Err(Error::CannotDecryptData);
+    }
+    let algorithm = Algorithm::from_id(envelope[ENVELOPE_MAGIC.len() + 1])?;
+
+    let (header, rest) = envelope.split_at(HEADER_LEN);
+    let wrapped_len = u16::from_be_bytes([rest[0], rest[1]]) as usize;
+    let rest = &rest[2..];
+    if rest.len() < wrapped_len + NONCE_LEN {
+        return Err(Error::CannotDecryptData);
+    }
+    let (wrapped_key, rest) = rest.split_at(wrapped_len);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+
+    match algorithm {
+        Algorithm::RsaAes256Gcm => {
+            let key = match private_key.decrypt(oaep(), wrapped_key) {
+                Ok(k) if k.len() == KEY_LEN => k,
+                _ => return Err(Error::CannotDecryptData),
+            };
+
+            let cipher = Aes256Gcm::new(Key::from_slice(&key));
+            let payload = Payload {
+                msg: ciphertext,
+                aad: header,
+            };
+            match cipher.decrypt(Nonce::from_slice(nonce), payload) {
+                Ok(p) => Ok(p),
+                Err(_) => Err(Error::CannotDecryptData),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let private_key = RsaPrivateKey::new(&mut OsRng, 2048).unwrap();
+        let public_key = RsaPublicKey::from(&private_key);
+        let plaintext = "a".repeat(1024);
+
+        let envelope = encrypt(plaintext.as_bytes(), &public_key, Algorithm::RsaAes256Gcm).unwrap();
+        assert!(is_envelope(&envelope));
+        assert!(envelope.len() < plaintext.len() + 512);
+        assert_eq!(
+            plaintext.as_bytes(),
+            decrypt(&envelope, &private_key).unwrap().as_slice()
+        );
+
+        let mut tampered = envelope.clone();
+        tampered[5] = 0;
+        assert_eq!(Error::CannotDecryptData, decrypt(&tampered, &private_key).unwrap_err());
+
+        // Flipping a byte of the nonce, the ciphertext or its tag must be detected.
+        let wrapped_len = u16::from_be_bytes([envelope[HEADER_LEN], envelope[HEADER_LEN + 1]]) as usize;
+        let nonce_start = HEADER_LEN + 2 + wrapped_len;
+        for &i in &[nonce_start, nonce_start + NONCE_LEN, envelope.len() - 1] {
+            let mut tampered = envelope.clone();
+            tampered[i] ^= 0x01;
+            assert_eq!(Error::CannotDecryptData, decrypt(&tampered, &private_key).unwrap_err());
+        }
+        assert_eq!(
+            Error::CannotDecryptData,
+            decrypt(&envelope[..nonce_start + 4], &private_key).unwrap_err()
+        );
+
+        let other_key = RsaPrivateKey::new(&mut OsRng, 2048).unwrap();
+        assert_eq!(Error::CannotDecryptData, decrypt(&envelope, &other_key).unwrap_err());
+    }
+}
@@ -1,9 +1,231 @@
 /// Convenient type alias of std's `Result`.
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Exit code for an unknown or unexpected failure.
+pub const EXIT_GENERIC: i32 = 1;
+
+/// Exit code for invalid arguments or user input.
+pub const EXIT_INVALID_INPUT: i32 = 2;
+
+/// Exit code for a node that is unreachable or fails to serve a request.
+pub const EXIT_NODE_FAILURE: i32 = 3;
+
+/// Exit code for a message or resource that cannot be found.
+pub const EXIT_NOT_FOUND: i32 = 4;
+
+/// Exit code for a message whose contents cannot be handled.
+pub const EXIT_INVALID_MESSAGE: i32 = 5;
+
+/// Exit code for a message that was not confirmed in time.
+pub const EXIT_TIMEOUT: i32 = 6;
+
+/// Exit code for a message that was confirmed as conflicting.
+pub const EXIT_CONFLICT: i32 = 7;
+
 /// Error types for `tio`.
 #[derive(Debug, PartialEq, thiserror::Error)]
 pub enum Error {
+    // Unknown or unexpected failures (`EXIT_GENERIC`).
+    /// An (unknown) error occurred when executing some command.
+    #[error("Something went wrong")]
+    Generic,
+
+    /// The local proof of work failed.
+    #[error("Unable to do the proof of work locally")]
+    CannotDoPow,
+
+    /// The location of the keystore cannot be determined.
+    #[error("Unable to determine the location of the keystore")]
+    KeystoreUnavailable,
+
+    /// The key is unable to be generated.
+    #[error("Unable to generate the key")]
+    CannotGenerateKey,
+
+    /// The key is unable to be written.
+    #[error("Unable to write the key to \"{0}\"")]
+    CannotWriteKey(String),
+
+    /// The data is unable to be written.
+    #[error("Unable to write the data to \"{0}\"")]
+    CannotWriteData(String),
+
+    // Invalid arguments or user input (`EXIT_INVALID_INPUT`).
+    /// The Bech32 address is invalid.
+    #[error("\"{0}\" is not a valid Bech32 address")]
+    AddressInvalid(String),
+
+    /// The Bech32 address belongs to another network than the node.
+    #[error("The address is for the \"{0}\" network, but the node is on the \"{1}\" network")]
+    AddressNetworkMismatch(String, String),
+
+    /// The output ID is invalid.
+    #[error("\"{0}\" is not a valid output ID (must be a hexadecimal string of exactly 34 bytes)")]
+    OutputIdInvalid(String),
+
+    /// The milestone index is invalid.
+    #[error("\"{0}\" is not a valid milestone, must be an index or \"latest\"")]
+    MilestoneInvalid(String),
+
+    /// The range of milestone indexes is invalid.
+    #[error("\"{0}\" is not a valid milestone range, must be of the form \"start..end\" with start <= end")]
+    MilestoneRangeInvalid(String),
+
+    /// The range of milestone indexes spans too many milestones.
+    #[error("The range spans {0} milestones, but at most {1} milestones can be shown at once")]
+    MilestoneRangeTooLarge(u64, u32),
+
+    /// No topic to subscribe to was given.
+    #[error("At least one topic to subscribe to must be given")]
+    SubscriptionTopicMissing,
+
+    /// The page or page size is invalid.
+    #[error("\"{0}\" is not a valid page, must be a positive integer")]
+    PageInvalid(String),
+
+    /// The node URL is invalid.
+    #[error("Unable to parse the node URL")]
+    CannotParseNodeUrl,
+
+    /// Specified IOTA network is invalid.
+    #[error(
+        "\"{0}\" is not a valid network, must be \"mainnet\", \"devnet\" or a network from the configuration file"
+    )]
+    NetworkInvalid(String),
+
+    /// There is no node to send requests to.
+    #[error("No node URL to send API requests to is given")]
+    NodeUrlMissing,
+
+    /// The hash of the message is invalid.
+    #[error("\"{0}\" is not a valid message ID hash")]
+    MessageHashInvalid(String),
+
+    /// The number of proof of work threads is invalid.
+    #[error("\"{0}\" is not a valid number of proof of work threads, must be a positive integer")]
+    PowWorkersInvalid(String),
+
+    /// The number of proof of work samples is invalid.
+    #[error("\"{0}\" is not a valid number of samples, must be a positive integer")]
+    PowSamplesInvalid(String),
+
+    /// The proof of work score is invalid.
+    #[error("\"{0}\" is not a valid proof of work score, must be a positive number")]
+    PowScoreInvalid(String),
+
+    /// The data contents of the message is too large.
+    #[error("The message data must be < 4kb, found {0} bytes")]
+    MessageDataTooLarge(usize),
+
+    /// The data index of the message is too large.
+    #[error("The message data index must be between 1-64 bytes long, found {0} bytes")]
+    MessageDataIndexTooLarge(usize),
+
+    /// The target rate of the spammer is invalid.
+    #[error("\"{0}\" is not a valid rate, must be between 0.000001 and 1000000 messages per second")]
+    SpamRateInvalid(String),
+
+    /// The concurrency level of the spammer is invalid.
+    #[error("\"{0}\" is not a valid concurrency level, must be a positive integer")]
+    SpamConcurrencyInvalid(String),
+
+    /// The output format is invalid.
+    #[error("\"{0}\" is not a valid output format, must be \"text\" or \"json\"")]
+    OutputFormatInvalid(String),
+
+    /// The configuration file is unable to be read or parsed.
+    #[error("Unable to load the configuration file: {0}")]
+    ConfigInvalid(String),
+
+    /// The requested profile does not exist in the configuration file.
+    #[error("The profile \"{0}\" cannot be found in the configuration file")]
+    ProfileNotFound(String),
+
+    /// The quorum is invalid.
+    #[error("\"{0}\" is not a valid quorum, must be a positive integer")]
+    QuorumInvalid(String),
+
+    /// The quorum is larger than the number of nodes to ask.
+    #[error("A quorum of {0} cannot be reached with only {1} node(s) to ask")]
+    QuorumTooLarge(usize, usize),
+
+    /// Only one of the username and password for basic authentication was given.
+    #[error("Both a username and a password are required for basic authentication with the node")]
+    NodeAuthIncomplete,
+
+    /// The key is unable to be read or is not a valid RSA key.
+    #[error("Unable to read an RSA key from \"{0}\"")]
+    KeyInvalid(String),
+
+    /// The encryption algorithm is invalid.
+    #[error("\"{0}\" is not a valid encryption algorithm")]
+    EncryptionAlgorithmInvalid(String),
+
+    /// The data is unable to be encrypted.
+    #[error("Unable to encrypt the message data")]
+    CannotEncryptData,
+
+    /// The key alias is invalid.
+    #[error("\"{0}\" is not a valid key alias, must be 1-64 letters, digits, '-' or '_'")]
+    KeyAliasInvalid(String),
+
+    /// The key size is invalid.
+    #[error("\"{0}\" is not a valid key size, must be 2048, 3072 or 4096 bits")]
+    KeyBitsInvalid(String),
+
+    /// The key format is invalid.
+    #[error("\"{0}\" is not a valid key format, must be \"pem\" or \"der\"")]
+    KeyFormatInvalid(String),
+
+    /// The passphrase of a key is missing or wrong.
+    #[error("The key passphrase is missing or incorrect")]
+    KeyPassphraseInvalid,
+
+    /// A key with the alias already exists in the keystore.
+    #[error("A key with the alias \"{0}\" already exists")]
+    KeyAlreadyExists(String),
+
+    /// The file to export a key to already exists.
+    #[error("The file \"{0}\" already exists, use --force to overwrite it")]
+    KeyFileExists(String),
+
+    /// The data encoding is invalid.
+    #[error("\"{0}\" is not a valid data encoding, must be \"utf8\", \"hex\" or \"base64\"")]
+    DataEncodingInvalid(String),
+
+    /// The data display format is invalid.
+    #[error("\"{0}\" is not a valid data format, must be \"auto\", \"utf8\", \"hex\", \"base64\", \"hexdump\", \"json\", \"cbor\" or \"msgpack\"")]
+    DataFormatInvalid(String),
+
+    /// The data cannot be decoded with the given encoding.
+    #[error("The data is not valid {0}")]
+    CannotDecodeData(String),
+
+    /// The data file is unable to be read.
+    #[error("Unable to read the data from \"{0}\"")]
+    CannotReadData(String),
+
+    // Nodes that are unreachable or fail to serve a request (`EXIT_NODE_FAILURE`).
+    /// The balance of an address is unable to be retrieved.
+    #[error("Unable to retrieve the balance of the address")]
+    CannotGetAddressBalance,
+
+    /// The outputs of an address are unable to be retrieved.
+    #[error("Unable to retrieve the outputs of the address")]
+    CannotGetAddressOutputs,
+
+    /// An output is unable to be retrieved.
+    #[error("Unable to retrieve the output {0}")]
+    CannotGetOutput(String),
+
+    /// A milestone is unable to be retrieved.
+    #[error("Unable to retrieve the milestone {0}")]
+    CannotGetMilestone(u32),
+
+    /// The node is unable to be subscribed to.
+    #[error("Unable to subscribe to the node's event stream")]
+    CannotSubscribe,
+
     /// The message is unable to be broadcasted.
     #[error("Unable to broadcast message")]
     CannotBroadcastMessage,
@@ -20,18 +242,64 @@ pub enum Error {
     #[error("Unable to retrieve the node information")]
     CannotGetNodeInfo,
 
-    /// The node URL is invalid.
-    #[error("Unable to parse the node URL")]
-    CannotParseNodeUrl,
+    /// The node reports another network than expected.
+    #[error("The node is on the \"{1}\" network, but the \"{0}\" network is expected")]
+    NetworkMismatch(String, String),
 
-    /// An (unknown) error occurred when executing some command.
-    #[error("Something went wrong")]
-    Generic,
+    /// The node reports another Bech32 HRP than expected.
+    #[error("The node uses the \"{1}\" Bech32 HRP, but \"{0}\" is expected")]
+    Bech32HrpMismatch(String, String),
 
-    /// Specified IOTA network is invalid.
-    #[error("\"{0}\" is not a valid network")]
-    NetworkInvalid(String),
+    /// The messages of an index are unable to be retrieved.
+    #[error("Unable to search for messages by index")]
+    CannotSearchIndex,
+
+    /// The message is unable to be retrieved, for another reason than it not being found.
+    #[error("Unable to retrieve the message")]
+    CannotGetMessage,
+
+    /// The metadata of a message is unable to be retrieved.
+    #[error("Unable to retrieve the message metadata")]
+    CannotGetMessageMetadata,
+
+    /// The message is unable to be promoted.
+    #[error("Unable to promote the message")]
+    CannotPromoteMessage,
+
+    /// The message is unable to be reattached.
+    #[error("Unable to reattach the message")]
+    CannotReattachMessage,
+
+    /// Some of the messages are unable to be promoted or reattached.
+    #[error("{0} message(s) could not be promoted or reattached")]
+    RemedyIncomplete(usize),
+
+    /// Not enough nodes agreed on the result.
+    #[error("Only {0} node(s) agreed on the result, but a quorum of {1} is required")]
+    QuorumNotReached(usize, usize),
+
+    /// None of the nodes to choose from is healthy and synced.
+    #[error("None of the {0} node(s) is reachable, healthy, synced and on the expected network")]
+    NoHealthyNode(usize),
+
+    // Messages or resources that cannot be found (`EXIT_NOT_FOUND`).
+    /// The output cannot be found given its ID.
+    #[error("The output {0} cannot be found")]
+    OutputNotFound(String),
+
+    /// The milestone cannot be found given its index.
+    #[error("The milestone {0} cannot be found")]
+    MilestoneNotFound(u32),
+
+    /// The message cannot be found given a hash ID.
+    #[error("The message cannot be found")]
+    MessageNotFound,
+
+    /// The key cannot be found in the keystore.
+    #[error("The key \"{0}\" cannot be found in the keystore")]
+    KeyNotFound(String),
 
+    // Messages whose contents cannot be handled (`EXIT_INVALID_MESSAGE`).
     /// The data within a message's indexation payload is invalid.
     #[error("The data in the message is not valid")]
     MessageDataInvalid,
@@ -44,22 +312,6 @@ pub enum Error {
     #[error("The message contains no data")]
     MessageEmpty,
 
-    /// The hash of the message is invalid.
-    #[error("\"{0}\" is not a valid message ID hash")]
-    MessageHashInvalid(String),
-
-    /// The message cannot be found given a hash ID.
-    #[error("The message cannot be found")]
-    MessageNotFound,
-
-    /// The data contents of the message is too large.
-    #[error("The message data must be < 4kb, found {0} bytes")]
-    MessageDataTooLarge(usize),
-
-    /// The data index of the message is too large.
-    #[error("The message data index must be between 1-64 bytes long, found {0} bytes")]
-    MessageDataIndexTooLarge(usize),
-
     /// The payload type of the message is wrong.
     #[error("The message's payload type is wrong (should be Indexation)")]
     MessageWrongPayload,
@@ -67,4 +319,127 @@ pub enum Error {
     /// The transaction essence is invalid.
     #[error("The message's transaction essence is invalid")]
     MessageEssenceInvalid,
+
+    /// The data is unable to be decrypted.
+    #[error("Unable to decrypt the message data (is it encrypted for this key?)")]
+    CannotDecryptData,
+
+    // Messages that are not confirmed or sent in time (`EXIT_TIMEOUT`).
+    /// The message was not referenced by a milestone before the timeout.
+    #[error("The message was not confirmed within {0} seconds")]
+    ConfirmationTimeout(u64),
+
+    /// The message, including its proof of work, was not sent before the timeout.
+    #[error("The message was not sent within {0} seconds, including its proof of work")]
+    PowTimeout(u64),
+
+    // Messages that are confirmed as conflicting (`EXIT_CONFLICT`).
+    /// The message was referenced by a milestone but conflicts with the ledger.
+    #[error("The message is conflicting ({0})")]
+    MessageConflicting(String),
+}
+
+impl Error {
+    /// The stable process exit code associated with the error.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Generic
+            | Self::CannotDoPow
+            | Self::KeystoreUnavailable
+            | Self::CannotGenerateKey
+            | Self::CannotWriteKey(_)
+            | Self::CannotWriteData(_) => EXIT_GENERIC,
+
+            Self::AddressInvalid(_)
+            | Self::AddressNetworkMismatch(_, _)
+            | Self::OutputIdInvalid(_)
+            | Self::MilestoneInvalid(_)
+            | Self::MilestoneRangeInvalid(_)
+            | Self::MilestoneRangeTooLarge(_, _)
+            | Self::SubscriptionTopicMissing
+            | Self::PageInvalid(_)
+            | Self::CannotParseNodeUrl
+            | Self::NetworkInvalid(_)
+            | Self::NodeUrlMissing
+            | Self::MessageHashInvalid(_)
+            | Self::PowWorkersInvalid(_)
+            | Self::PowSamplesInvalid(_)
+            | Self::PowScoreInvalid(_)
+            | Self::MessageDataTooLarge(_)
+            | Self::MessageDataIndexTooLarge(_)
+            | Self::SpamRateInvalid(_)
+            | Self::SpamConcurrencyInvalid(_)
+            | Self::OutputFormatInvalid(_)
+            | Self::ConfigInvalid(_)
+            | Self::ProfileNotFound(_)
+            | Self::QuorumInvalid(_)
+            | Self::QuorumTooLarge(_, _)
+            | Self::NodeAuthIncomplete
+            | Self::KeyInvalid(_)
+            | Self::EncryptionAlgorithmInvalid(_)
+            | Self::CannotEncryptData
+            | Self::KeyAliasInvalid(_)
+            | Self::KeyBitsInvalid(_)
+            | Self::KeyFormatInvalid(_)
+            | Self::KeyPassphraseInvalid
+            | Self::KeyAlreadyExists(_)
+            | Self::KeyFileExists(_)
+            | Self::DataEncodingInvalid(_)
+            | Self::DataFormatInvalid(_)
+            | Self::CannotDecodeData(_)
+            | Self::CannotReadData(_) => EXIT_INVALID_INPUT,
+
+            Self::CannotGetAddressBalance
+            | Self::CannotGetAddressOutputs
+            | Self::CannotGetOutput(_)
+            | Self::CannotGetMilestone(_)
+            | Self::CannotSubscribe
+            | Self::CannotBroadcastMessage
+            | Self::CannotBuildNodeClient
+            | Self::CannotGetNetworkInfo
+            | Self::CannotGetNodeInfo
+            | Self::NetworkMismatch(_, _)
+            | Self::Bech32HrpMismatch(_, _)
+            | Self::CannotSearchIndex
+            | Self::CannotGetMessage
+            | Self::CannotGetMessageMetadata
+            | Self::CannotPromoteMessage
+            | Self::CannotReattachMessage
+            | Self::RemedyIncomplete(_)
+            | Self::QuorumNotReached(_, _)
+            | Self::NoHealthyNode(_) => EXIT_NODE_FAILURE,
+
+            Self::OutputNotFound(_) | Self::MilestoneNotFound(_) | Self::MessageNotFound | Self::KeyNotFound(_) => {
+                EXIT_NOT_FOUND
+            }
+
+            Self::MessageDataInvalid
+            | Self::MessageDataIndexInvalid
+            | Self::MessageEmpty
+            | Self::MessageWrongPayload
+            | Self::MessageEssenceInvalid
+            | Self::CannotDecryptData => EXIT_INVALID_MESSAGE,
+
+            Self::ConfirmationTimeout(_) | Self::PowTimeout(_) => EXIT_TIMEOUT,
+
+            Self::MessageConflicting(_) => EXIT_CONFLICT,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_code() {
+        assert_eq!(EXIT_GENERIC, Error::Generic.exit_code());
+        assert_eq!(EXIT_INVALID_INPUT, Error::NetworkInvalid(String::from("")).exit_code());
+        assert_eq!(EXIT_NODE_FAILURE, Error::CannotBuildNodeClient.exit_code());
+        assert_eq!(EXIT_NOT_FOUND, Error::MessageNotFound.exit_code());
+        assert_eq!(EXIT_NODE_FAILURE, Error::CannotGetMessage.exit_code());
+        assert_eq!(EXIT_INVALID_MESSAGE, Error::MessageWrongPayload.exit_code());
+        assert_eq!(EXIT_TIMEOUT, Error::ConfirmationTimeout(60).exit_code());
+        assert_eq!(EXIT_CONFLICT, Error::MessageConflicting(String::new()).exit_code());
+    }
 }
@@ -0,0 +1,212 @@
+use std::time::{Duration, Instant};
+
+use iota_client::{bee_message::MessageId, bee_rest_api::types::responses::MessageMetadataResponse, Client};
+
+use crate::{
+    error::{Error, Result},
+    iota::{
+        client::{build_client, ClientOptions},
+        views::{ConfirmationView, MessageStatus, MetadataView, RemedyOutcome, RemedyView},
+    },
+};
+
+/// Number of seconds to wait between checks of a message's confirmation.
+pub const CONFIRMATION_POLL_INTERVAL: u64 = 5;
+
+/// The action taken to get a stuck message confirmed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Remedy {
+    /// A new message was attached that approves the stuck message.
+    Promoted(MessageId),
+    /// The stuck message's payload was attached again as a new message.
+    Reattached(MessageId),
+}
+
+/// The action requested for a message that is not yet confirmed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RemedyAction {
+    Promote,
+    Reattach,
+}
+
+/// Retrieve the metadata of a message.
+pub(crate) async fn get_metadata(iota: &Client, id: &MessageId) -> Result<MessageMetadataResponse> {
+    match iota.get_message().metadata(id).await {
+        Ok(m) => Ok(m),
+        Err(_) => Err(Error::CannotGetMessageMetadata),
+    }
+}
+
+/// Promote or reattach a message if its metadata suggests that it is needed, preferring reattachment.
+pub(crate) async fn remedy_message(
+    iota: &Client,
+    id: &MessageId,
+    metadata: &MessageMetadataResponse,
+) -> Result<Option<Remedy>> {
+    if metadata.should_reattach == Some(true) {
+        return match iota.reattach_unchecked(id).await {
+            Ok((new_id, _)) => Ok(Some(Remedy::Reattached(new_id))),
+            Err(_) => Err(Error::CannotReattachMessage),
+        };
+    }
+    if metadata.should_promote == Some(true) {
+        return match iota.promote_unchecked(id).await {
+            Ok((new_id, _)) => Ok(Some(Remedy::Promoted(new_id))),
+            Err(_) => Err(Error::CannotPromoteMessage),
+        };
+    }
+
+    Ok(None)
+}
+
+async fn remedy_with(iota: &Client, id: &MessageId, action: RemedyAction, force: bool) -> RemedyView {
+    let mut view = RemedyView {
+        id: id.to_string(),
+        outcome: RemedyOutcome::Skipped,
+        new_id: None,
+        reason: None,
+    };
+
+    let metadata = match get_metadata(iota, id).await {
+        Ok(m) => m,
+        Err(e) => {
+            view.outcome = RemedyOutcome::Failed;
+            view.reason = Some(e.to_string());
+            return view;
+        }
+    };
+    if let Some(index) = metadata.referenced_by_milestone_index {
+        view.reason = Some(format!("already referenced by milestone {}", index));
+        return view;
+    }
+
+    let (needed, alternative) = match action {
+        RemedyAction::Promote => (metadata.should_promote, metadata.should_reattach),
+        RemedyAction::Reattach => (metadata.should_reattach, metadata.should_promote),
+    };
+    if needed != Some(true) && !force {
+        view.reason = Some(match (action, alternative) {
+            (RemedyAction::Promote, Some(true)) => String::from("the node suggests reattaching it instead"),
+            (RemedyAction::Reattach, Some(true)) => String::from("the node suggests promoting it instead"),
+            _ => String::from("the node does not consider it stale"),
+        });
+        return view;
+    }
+
+    let result = match action {
+        RemedyAction::Promote => iota.promote_unchecked(id).await,
+        RemedyAction::Reattach => iota.reattach_unchecked(id).await,
+    };
+    match (result, action) {
+        (Ok((new_id, _)), RemedyAction::Promote) => {
+            view.outcome = RemedyOutcome::Promoted;
+            view.new_id = Some(new_id.to_string());
+        }
+        (Ok((new_id, _)), RemedyAction::Reattach) => {
+            view.outcome = RemedyOutcome::Reattached;
+            view.new_id = Some(new_id.to_string());
+        }
+        (Err(_), RemedyAction::Promote) => {
+            view.outcome = RemedyOutcome::Failed;
+            view.reason = Some(Error::CannotPromoteMessage.to_string());
+        }
+        (Err(_), RemedyAction::Reattach) => {
+            view.outcome = RemedyOutcome::Failed;
+            view.reason = Some(Error::CannotReattachMessage.to_string());
+        }
+    }
+
+    view
+}
+
+/// Promote or reattach each of the given messages when the node suggests it (or regardless, if forced), where
+/// messages that are already referenced by a milestone are always skipped.
+pub async fn remedy_messages(
+    message_ids: &[[u8; 32]],
+    action: RemedyAction,
+    force: bool,
+    client: &ClientOptions,
+) -> Result<Vec<RemedyView>> {
+    let iota = build_client(client).await?;
+
+    let mut views = Vec::with_capacity(message_ids.len());
+    for id in message_ids {
+        views.push(remedy_with(&iota, &MessageId::new(*id), action, force).await);
+    }
+
+    Ok(views)
+}
+
+/// Wait until a message (or one of its reattachments) is referenced by a milestone, promoting or reattaching it
+/// whenever it becomes stale, and report its state once it is referenced or the timeout is reached.
+pub async fn wait_for_confirmation(
+    message_id: &[u8; 32],
+    timeout: Duration,
+    client: &ClientOptions,
+) -> Result<ConfirmationView> {
+    let iota = build_client(client).await?;
+    let start = Instant::now();
+    let interval = Duration::from_secs(CONFIRMATION_POLL_INTERVAL);
+
+    let original = MessageId::new(*message_id);
+    let mut attachments = vec![original];
+    let mut view = ConfirmationView {
+        id: original.to_string(),
+        confirmed_id: None,
+        status: MessageStatus::Pending,
+        metadata: None,
+        promotions: Vec::new(),
+        reattachments: Vec::new(),
+        elapsed_seconds: 0.0,
+    };
+
+    loop {
+        // Any attachment may be the one that ends up referenced, not only the latest.
+        let mut latest = None;
+        for id in &attachments {
+            let metadata = match get_metadata(&iota, id).await {
+                Ok(m) => m,
+                Err(e) => {
+                    eprintln!("warning: {} ({})", e, id);
+                    continue;
+                }
+            };
+            if let Some(index) = metadata.referenced_by_milestone_index {
+                let timestamp = iota.get_milestone(index).await.ok().map(|m| m.timestamp);
+                let metadata = MetadataView::new(&metadata, timestamp);
+                view.status = metadata.status;
+                view.confirmed_id = Some(id.to_string());
+                view.metadata = Some(metadata);
+                view.elapsed_seconds = start.elapsed().as_secs_f64();
+
+                return Ok(view);
+            }
+            if id == attachments.last().unwrap_or(&original) {
+                latest = Some(metadata);
+            }
+        }
+
+        if start.elapsed() >= timeout {
+            view.metadata = latest.map(|m| MetadataView::new(&m, None));
+            view.elapsed_seconds = start.elapsed().as_secs_f64();
+
+            return Ok(view);
+        }
+
+        let latest_id = *attachments.last().unwrap_or(&original);
+        if let Some(metadata) = &latest {
+            match remedy_message(&iota, &latest_id, metadata).await {
+                Ok(Some(Remedy::Promoted(id))) => view.promotions.push(id.to_string()),
+                Ok(Some(Remedy::Reattached(id))) => {
+                    view.reattachments.push(id.to_string());
+                    attachments.push(id);
+                }
+                Ok(None) => (),
+                Err(e) => eprintln!("warning: {} ({})", e, latest_id),
+            }
+        }
+
+        let remaining = timeout.checked_sub(start.elapsed()).unwrap_or_default();
+        tokio::time::sleep(interval.min(remaining)).await;
+    }
+}
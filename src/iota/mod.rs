@@ -1,176 +1,478 @@
-use chrono::{DateTime, NaiveDateTime, Utc};
-use iota_client::bee_message::{
-    address::Address,
-    input::Input,
-    output::Output,
-    payload::Payload,
-    prelude::{Essence, IndexationPayload, TransactionPayload},
-    Message, MessageId,
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    str::FromStr,
+    time::{Duration, Instant},
 };
 
-use crate::{error::Error, iota::client::build_client};
+use chrono::Local;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use iota_client::{
+    bee_message::{input::UtxoInput, payload::Payload, Message, MessageId},
+    bee_rest_api::types::dtos::OutputDto,
+    node::OutputsOptions,
+    Client,
+};
+
+use crate::{
+    error::{Error, Result},
+    iota::{
+        client::{build_client, redact_url, ClientOptions},
+        confirm::get_metadata,
+        views::{
+            AddressEvent, AddressSummary, BroadcastView, DataView, IndexSearchView, InputView, LedgerOutputView,
+            MessageView, MetadataView, MilestoneDetails, MilestoneSummary, NetworkSummary, NodeDetails, NodeSummary,
+            OutputPage, PayloadView,
+        },
+    },
+};
 
 pub mod client;
+pub mod confirm;
+pub mod pow;
+pub mod quorum;
+pub mod spam;
+pub mod subscribe;
+pub mod views;
+
+/// Send a data message with the given index using an existing client.
+pub(crate) async fn send_message(iota: &Client, index: &str, data: &[u8]) -> Result<MessageId> {
+    match iota.message().with_index(index).with_data(data.to_vec()).finish().await {
+        Ok(m) => Ok(m.id().0),
+        Err(_) => Err(Error::CannotBroadcastMessage),
+    }
+}
 
 /// Broadcast a message with given data to a specific IOTA network.
-pub async fn broadcast_message(index: &str, data: &str, node_url: &str) -> MessageId {
-    let iota = build_client(node_url).await;
-    let m = match iota
-        .message()
-        .with_index(index)
-        .with_data(data.as_bytes().to_vec())
-        .finish()
-        .await
-    {
-        Ok(m) => m,
-        Err(_) => panic!("{:?}", Error::CannotBroadcastMessage),
+pub async fn broadcast_message(index: &str, data: &[u8], client: &ClientOptions) -> Result<BroadcastView> {
+    let iota = build_client(client).await?;
+    let start = Instant::now();
+    let message_id = send_message(&iota, index, data).await?;
+    let elapsed = start.elapsed();
+
+    Ok(BroadcastView {
+        id: message_id.to_string(),
+        index: index.to_string(),
+        data: data.to_vec(),
+        size: index.as_bytes().len() + data.len(),
+        encryption: None,
+        explorer_url: client
+            .explorer
+            .as_ref()
+            .map(|e| format!("{}/message/{}", e.trim_end_matches('/'), message_id)),
+        elapsed_seconds: elapsed.as_secs_f64(),
+        confirmation: None,
+    })
+}
+
+/// Search for a message on a specified IOTA network given its hash ID.
+///
+/// With `resolve_inputs`, the outputs spent by a transaction are fetched as well.
+pub async fn find_message(message_id: &[u8; 32], resolve_inputs: bool, client: &ClientOptions) -> Result<MessageView> {
+    let iota = build_client(client).await?;
+
+    fetch_message(&iota, &MessageId::new(*message_id), resolve_inputs).await
+}
+
+/// Search for the IDs of the messages with the given index, optionally fetching each of them as well.
+pub async fn find_messages_by_index(
+    index: &[u8],
+    limit: Option<usize>,
+    fetch: bool,
+    resolve_inputs: bool,
+    client: &ClientOptions,
+) -> Result<IndexSearchView> {
+    let iota = build_client(client).await?;
+    let mut message_ids = match iota.get_message().index(index).await {
+        Ok(ids) => ids,
+        Err(_) => return Err(Error::CannotSearchIndex),
     };
+    // Nodes list the IDs in no particular order, so they are sorted for the limit to select the same ones on every
+    // node.
+    message_ids.sort_by_key(|id| id.to_string());
+    let total = message_ids.len();
+    let message_ids = &message_ids[..limit.unwrap_or(total).min(total)];
 
-    let size = index.as_bytes().len() + data.as_bytes().len();
-    println!(
-        "--- Data Message ---\n\
-        ID: {}\n\
-        Index: {}\n\
-        Data: {}\n\
-        Size: {} byte(s)",
-        m.id().0,
-        index,
-        data,
-        size,
-    );
-
-    m.id().0
-}
-
-fn print_message_payload(payload: &Payload) {
-    match payload {
-        Payload::Indexation(p) => unsafe {
-            let data_payload: &IndexationPayload = p.as_ref();
-
-            let index = match String::from_utf8(data_payload.index().iter().cloned().collect()) {
-                Ok(s) => s,
-                Err(_) => panic!("{:?}", Error::MessageDataIndexInvalid),
-            };
-            let data = match String::from_utf8(data_payload.data().iter().cloned().collect()) {
-                Ok(s) => s,
-                Err(_) => String::from_utf8_unchecked(data_payload.data().iter().cloned().collect()),
-            };
-            let size = index.as_bytes().len() + data.as_bytes().len();
-
-            println!(
-                "--- Data Payload ---\n\
-                Index: {}\n\
-                Data: {}\n\
-                Size: {} byte(s)",
-                index, data, size,
-            )
-        },
-        Payload::Transaction(p) => {
-            println!("--- UTXO Payload ---");
-
-            let tx: &TransactionPayload = p.as_ref();
-            match tx.essence() {
-                Essence::Regular(e) => {
-                    if e.inputs().len() > 0 {
-                        println!("Input(s):");
-                        for input in e.inputs() {
-                            match input {
-                                Input::Utxo(i) => {
-                                    println!("{}", i.to_string());
-                                }
-                                _ => (),
-                            }
-                        }
-                    }
-
-                    if e.outputs().len() > 0 {
-                        println!("\nOutput(s):");
-                        for output in e.outputs() {
-                            match output {
-                                Output::SignatureLockedSingle(sls) => match sls.address() {
-                                    Address::Ed25519(a) => {
-                                        println!(
-                                            "Address: {}, Type: {}, Amount: {}i",
-                                            a.to_string(),
-                                            "Ed25519".to_string(),
-                                            sls.amount()
-                                        );
-                                    }
-                                },
-                                _ => (),
-                            }
-                        }
-                    }
-
-                    match e.payload() {
-                        Some(payload) => {
-                            println!();
-                            print_message_payload(payload)
-                        }
-                        None => (),
-                    }
-                }
+    let mut messages = None;
+    if fetch {
+        let mut fetched = Vec::with_capacity(message_ids.len());
+        for id in message_ids {
+            // A single message that cannot be shown should not hide the others.
+            match fetch_message(&iota, id, resolve_inputs).await {
+                Ok(m) => fetched.push(m),
+                Err(e) => eprintln!("warning: skipping message {}: {}", id, e),
             }
         }
-        _ => panic!("{:?}", Error::MessageWrongPayload),
+        messages = Some(fetched);
     }
+
+    Ok(IndexSearchView {
+        index: DataView::new(index.to_vec()),
+        total,
+        message_ids: message_ids.iter().map(|id| id.to_string()).collect(),
+        messages,
+    })
 }
 
-/// Search for a message on a specified IOTA network given its hash ID.
-pub async fn find_message(message_id: &[u8; 32], node_url: &str) {
-    let iota = build_client(node_url).await;
-    let id = MessageId::new(*message_id);
-    let message: Message = match iota.get_message().data(&id).await {
+async fn fetch_message(iota: &Client, id: &MessageId, resolve_inputs: bool) -> Result<MessageView> {
+    let message: Message = match iota.get_message().data(id).await {
         Ok(m) => m,
-        Err(_) => panic!("{:?}", Error::MessageNotFound),
+        Err(iota_client::Error::ResponseError(404, _)) => return Err(Error::MessageNotFound),
+        Err(_) => return Err(Error::CannotGetMessage),
     };
     let payload: &Payload = match message.payload() {
         Some(p) => p,
-        None => panic!("{:?}", Error::MessageEmpty),
+        None => return Err(Error::MessageEmpty),
+    };
+
+    let metadata = get_metadata(iota, id).await?;
+    // The milestone may have been pruned by the node, in which case its timestamp is simply unknown.
+    let milestone_timestamp = match metadata.referenced_by_milestone_index {
+        Some(index) => iota.get_milestone(index).await.ok().map(|m| m.timestamp),
+        None => None,
     };
 
-    print_message_payload(payload)
+    let mut payload = PayloadView::try_from(payload)?;
+    if resolve_inputs {
+        self::resolve_inputs(iota, &mut payload).await;
+    }
+
+    Ok(MessageView {
+        id: id.to_string(),
+        payload,
+        metadata: MetadataView::new(&metadata, milestone_timestamp),
+    })
 }
 
 /// Query a node for its network information.
-pub async fn get_info(node_url: &str) {
-    let iota = build_client(node_url).await;
+pub async fn get_info(client: &ClientOptions) -> Result<NodeSummary> {
+    let iota = build_client(client).await?;
 
     let network_info = match iota.get_network_info().await {
         Ok(ni) => ni,
-        Err(_) => panic!("{:?}", Error::CannotGetNetworkInfo),
+        Err(_) => return Err(Error::CannotGetNetworkInfo),
+    };
+    let network_id = match network_info.network_id {
+        Some(id) => id,
+        None => return Err(Error::CannotGetNetworkInfo),
     };
     let node_info = match iota.get_info().await {
         Ok(ni) => ni,
-        Err(_) => panic!("{:?}", Error::CannotGetNodeInfo),
-    };
-
-    let timestamp = node_info
-        .nodeinfo
-        .latest_milestone_timestamp
-        .to_string()
-        .as_str()
-        .parse::<i64>()
-        .unwrap();
-    let datetime: DateTime<Utc> = DateTime::from_utc(NaiveDateTime::from_timestamp(timestamp, 0), Utc);
-
-    println!(
-        "--- Network Info ---\n\
-        ID: {}\n\
-        Bech32 HRP: {}\n\
-        \n--- Node Info ---\n\
-        URL: {}\n\
-        Software: {} {}\n\
-        Stats: {:.1} MPS @ {:.2}%\n\
-        Milestones: No. {} @ {}",
-        network_info.network_id.unwrap(),
-        network_info.bech32_hrp,
-        node_info.url,
-        node_info.nodeinfo.name,
-        node_info.nodeinfo.version,
-        node_info.nodeinfo.referenced_messages_per_second,
-        node_info.nodeinfo.referenced_rate,
-        node_info.nodeinfo.latest_milestone_index,
-        datetime.format("%Y-%m-%d %H:%M:%S"),
-    );
+        Err(_) => return Err(Error::CannotGetNodeInfo),
+    };
+
+    Ok(NodeSummary {
+        network: NetworkSummary {
+            id: network_id.to_string(),
+            bech32_hrp: network_info.bech32_hrp,
+        },
+        node: NodeDetails {
+            url: redact_url(&node_info.url),
+            name: node_info.nodeinfo.name,
+            version: node_info.nodeinfo.version,
+            referenced_messages_per_second: node_info.nodeinfo.referenced_messages_per_second,
+            referenced_rate: node_info.nodeinfo.referenced_rate,
+            latest_milestone: MilestoneSummary {
+                index: node_info.nodeinfo.latest_milestone_index,
+                timestamp: node_info.nodeinfo.latest_milestone_timestamp,
+            },
+        },
+    })
+}
+
+/// Fetch a milestone along with the changes it applied to the ledger, or the latest confirmed milestone if no index is
+/// given.
+pub async fn find_milestone(index: Option<u32>, client: &ClientOptions) -> Result<MilestoneDetails> {
+    let iota = build_client(client).await?;
+
+    let index = match index {
+        Some(i) => i,
+        None => match iota.get_info().await {
+            // The latest milestone may not be solid yet, so its ledger changes are not available.
+            Ok(ni) => ni.nodeinfo.confirmed_milestone_index,
+            Err(_) => return Err(Error::CannotGetNodeInfo),
+        },
+    };
+
+    fetch_milestone(&iota, index).await
+}
+
+/// Maximum number of milestones fetched at the same time for a range.
+const MILESTONE_FETCH_CONCURRENCY: usize = 8;
+
+/// Fetch every milestone within an inclusive range of indexes, in order, failing on the first milestone that cannot
+/// be fetched.
+pub async fn find_milestones(start: u32, end: u32, client: &ClientOptions) -> Result<Vec<MilestoneDetails>> {
+    let iota = build_client(client).await?;
+
+    stream::iter(start..=end)
+        .map(|index| fetch_milestone(&iota, index))
+        .buffered(MILESTONE_FETCH_CONCURRENCY)
+        .try_collect()
+        .await
+}
+
+async fn fetch_milestone(iota: &Client, index: u32) -> Result<MilestoneDetails> {
+    let milestone = match iota.get_milestone(index).await {
+        Ok(m) => m,
+        Err(iota_client::Error::ResponseError(404, _)) => return Err(Error::MilestoneNotFound(index)),
+        Err(_) => return Err(Error::CannotGetMilestone(index)),
+    };
+    let changes = match iota.get_milestone_utxo_changes(index).await {
+        Ok(c) => c,
+        Err(_) => return Err(Error::CannotGetMilestone(index)),
+    };
+
+    // Receipts are only part of the milestone payload, so the milestone's message has to be fetched as well.
+    let message_id = match MessageId::from_str(&milestone.message_id) {
+        Ok(id) => id,
+        Err(_) => return Err(Error::CannotGetMilestone(index)),
+    };
+    let message: Message = match iota.get_message().data(&message_id).await {
+        Ok(m) => m,
+        Err(_) => return Err(Error::CannotGetMilestone(index)),
+    };
+    let receipt = match message.payload() {
+        Some(Payload::Milestone(p)) => match p.essence().receipt() {
+            Some(r) => Some(PayloadView::try_from(r)?),
+            None => None,
+        },
+        _ => None,
+    };
+
+    Ok(MilestoneDetails {
+        index,
+        message_id: milestone.message_id,
+        timestamp: milestone.timestamp,
+        created_outputs: changes.created_outputs,
+        consumed_outputs: changes.consumed_outputs,
+        receipt,
+    })
+}
+
+fn output_dto_amount(output: &OutputDto) -> u64 {
+    match output {
+        OutputDto::SignatureLockedSingle(o) => o.amount,
+        OutputDto::SignatureLockedDustAllowance(o) => o.amount,
+        OutputDto::Treasury(o) => o.amount,
+    }
+}
+
+async fn get_bech32_hrp(iota: &Client) -> Result<String> {
+    match iota.get_bech32_hrp().await {
+        Ok(hrp) => Ok(hrp),
+        Err(_) => Err(Error::CannotGetNetworkInfo),
+    }
+}
+
+/// Retrieve an output from the ledger given its ID.
+pub(crate) async fn get_ledger_output(iota: &Client, output_id: &str, bech32_hrp: &str) -> Result<LedgerOutputView> {
+    let input = match UtxoInput::from_str(output_id) {
+        Ok(i) => i,
+        Err(_) => return Err(Error::OutputIdInvalid(output_id.to_string())),
+    };
+
+    match iota.get_output(&input).await {
+        Ok(o) => Ok(LedgerOutputView::new(output_id.to_string(), &o, bech32_hrp)),
+        Err(iota_client::Error::ResponseError(404, _)) => Err(Error::OutputNotFound(output_id.to_string())),
+        Err(_) => Err(Error::CannotGetOutput(output_id.to_string())),
+    }
+}
+
+/// Look up an output in the ledger given its ID.
+pub async fn find_output(output_id: &str, client: &ClientOptions) -> Result<LedgerOutputView> {
+    let iota = build_client(client).await?;
+    let bech32_hrp = get_bech32_hrp(&iota).await?;
+
+    get_ledger_output(&iota, output_id, &bech32_hrp).await
+}
+
+/// Replace the output IDs of a transaction's inputs with the outputs themselves.
+async fn resolve_inputs(iota: &Client, payload: &mut PayloadView) {
+    let transaction = match payload {
+        PayloadView::Transaction(t) => t,
+        _ => return,
+    };
+    // Inputs that cannot be resolved (e.g. outputs pruned by the node) are shown by their ID only, rather than
+    // hiding the message they are part of.
+    let bech32_hrp = match get_bech32_hrp(iota).await {
+        Ok(hrp) => hrp,
+        Err(e) => return eprintln!("warning: unable to resolve inputs: {}", e),
+    };
+
+    for input in transaction.inputs.iter_mut() {
+        match input {
+            InputView::Utxo { output_id, output } => match get_ledger_output(iota, output_id, &bech32_hrp).await {
+                Ok(o) => *output = Some(o),
+                Err(e) => eprintln!("warning: unable to resolve input {}: {}", output_id, e),
+            },
+            InputView::Treasury { .. } => {}
+        }
+    }
+}
+
+/// Check that a Bech32 address belongs to the network of the node, returning the network's human-readable part.
+pub(crate) async fn check_address_network(iota: &Client, address: &str) -> Result<String> {
+    let bech32_hrp = get_bech32_hrp(iota).await?;
+
+    match address.rsplit_once('1') {
+        Some((hrp, _)) if hrp == bech32_hrp => Ok(bech32_hrp),
+        Some((hrp, _)) => Err(Error::AddressNetworkMismatch(hrp.to_string(), bech32_hrp)),
+        None => Err(Error::AddressInvalid(address.to_string())),
+    }
+}
+
+/// Query the balance of an address along with a page of its outputs.
+pub async fn get_address(
+    address: &str,
+    page: usize,
+    page_size: usize,
+    include_spent: bool,
+    client: &ClientOptions,
+) -> Result<AddressSummary> {
+    let iota = build_client(client).await?;
+    let bech32_hrp = check_address_network(&iota, address).await?;
+
+    let response = match iota.get_address().balance(address).await {
+        Ok(r) => r,
+        Err(_) => return Err(Error::CannotGetAddressBalance),
+    };
+    let options = OutputsOptions {
+        include_spent,
+        output_type: None,
+    };
+    let mut output_ids = match iota.get_address().outputs(address, options).await {
+        Ok(ids) => ids,
+        Err(_) => return Err(Error::CannotGetAddressOutputs),
+    };
+    // Nodes list the IDs in no particular order, so they are sorted for a page to hold the same outputs on every node.
+    output_ids.sort_by_key(|id| id.to_string());
+
+    let mut outputs = Vec::new();
+    let skipped = (page - 1).saturating_mul(page_size);
+    for output_id in output_ids.iter().skip(skipped).take(page_size) {
+        outputs.push(get_ledger_output(&iota, &output_id.to_string(), &bech32_hrp).await?);
+    }
+
+    Ok(AddressSummary {
+        address: address.to_string(),
+        balance: response.balance,
+        dust_allowed: response.dust_allowed,
+        ledger_index: response.ledger_index,
+        outputs: OutputPage {
+            page,
+            page_size,
+            total: output_ids.len(),
+            outputs,
+        },
+    })
+}
+
+/// Tracked state of an address's outputs, keyed by output ID with the last known spent status.
+type AddressOutputs = HashMap<String, bool>;
+
+async fn poll_address<F: FnMut(AddressEvent)>(
+    iota: &Client,
+    address: &str,
+    outputs: &mut AddressOutputs,
+    balance: &mut Option<u64>,
+    on_event: &mut F,
+) {
+    let now = Local::now();
+    let time = now.format("%Y-%m-%d %H:%M:%S");
+    let timestamp = now.timestamp();
+    let is_initial = balance.is_none();
+
+    let options = OutputsOptions {
+        include_spent: true,
+        output_type: None,
+    };
+    let output_ids = match iota.get_address().outputs(address, options).await {
+        Ok(ids) => ids,
+        Err(_) => return eprintln!("[{}] {}", time, Error::CannotGetAddressOutputs),
+    };
+
+    for output_id in output_ids.iter() {
+        let key = output_id.to_string();
+        let is_known = match outputs.get(&key) {
+            Some(true) => continue,
+            Some(false) => true,
+            None => false,
+        };
+
+        let output = match iota.get_output(output_id).await {
+            Ok(o) => o,
+            Err(_) => {
+                eprintln!("[{}] Unable to retrieve output {}", time, key);
+                // Outputs that exist when watching starts are not new, even if their details are only retrieved later.
+                if is_initial {
+                    outputs.entry(key).or_insert(false);
+                }
+                continue;
+            }
+        };
+
+        if !is_initial && !is_known {
+            on_event(AddressEvent::NewOutput {
+                timestamp,
+                output_id: key.clone(),
+                amount: output_dto_amount(&output.output),
+                message_id: output.message_id.clone(),
+            });
+        }
+        if !is_initial && output.is_spent {
+            on_event(AddressEvent::SpentOutput {
+                timestamp,
+                output_id: key.clone(),
+            });
+        }
+
+        outputs.insert(key, output.is_spent);
+    }
+
+    let response = match iota.get_address().balance(address).await {
+        Ok(r) => r,
+        Err(_) => return eprintln!("[{}] {}", time, Error::CannotGetAddressBalance),
+    };
+    match *balance {
+        None => on_event(AddressEvent::Snapshot {
+            timestamp,
+            outputs: outputs.len(),
+            balance: response.balance,
+            dust_allowed: response.dust_allowed,
+        }),
+        Some(b) if b != response.balance => on_event(AddressEvent::BalanceChanged {
+            timestamp,
+            previous_balance: b,
+            balance: response.balance,
+        }),
+        _ => (),
+    }
+    *balance = Some(response.balance);
+}
+
+/// Watch a Bech32 address for new outputs, spent outputs, and balance changes until interrupted, passing each
+/// observed event to the given callback.
+pub async fn monitor_address<F: FnMut(AddressEvent)>(
+    address: &str,
+    interval: u64,
+    client: &ClientOptions,
+    mut on_event: F,
+) -> Result<()> {
+    let iota = build_client(client).await?;
+    check_address_network(&iota, address).await?;
+
+    let mut outputs: AddressOutputs = HashMap::new();
+    let mut balance: Option<u64> = None;
+    let mut ticker = tokio::time::interval(Duration::from_secs(interval.max(1)));
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+    loop {
+        tokio::select! {
+            _ = &mut ctrl_c => break,
+            _ = ticker.tick() => poll_address(&iota, address, &mut outputs, &mut balance, &mut on_event).await,
+        }
+    }
+
+    Ok(())
 }
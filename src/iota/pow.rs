@@ -0,0 +1,252 @@
+use std::{
+    f64::consts::LN_3,
+    future::Future,
+    io::{self, Write},
+    thread,
+    time::{Duration, Instant},
+};
+
+use bee_pow::providers::{miner::MinerBuilder, NonceProvider, NonceProviderBuilder};
+use rand::Rng;
+use serde::Serialize;
+use tokio::time::{interval, timeout};
+
+use crate::{
+    error::{Error, Result},
+    iota::client::{build_client, ClientOptions},
+};
+
+/// Number of bytes taken by the nonce at the end of a message.
+const NONCE_BYTES: usize = std::mem::size_of::<u64>();
+
+/// Number of trailing zero trits each benchmark sample is mined for.
+const BENCH_TRAILING_ZEROS: i32 = 12;
+
+/// How often the elapsed time is reported while a message is being sent.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+pub(crate) fn try_pow_workers_from_str(arg: &str) -> Result<usize> {
+    match arg.parse::<usize>() {
+        Ok(w) if w > 0 => Ok(w),
+        _ => Err(Error::PowWorkersInvalid(arg.to_string())),
+    }
+}
+
+pub(crate) fn try_pow_samples_from_str(arg: &str) -> Result<usize> {
+    match arg.parse::<usize>() {
+        Ok(s) if s > 0 => Ok(s),
+        _ => Err(Error::PowSamplesInvalid(arg.to_string())),
+    }
+}
+
+pub(crate) fn try_pow_score_from_str(arg: &str) -> Result<f64> {
+    match arg.parse::<f64>() {
+        Ok(s) if s.is_finite() && s > 0.0 => Ok(s),
+        _ => Err(Error::PowScoreInvalid(arg.to_string())),
+    }
+}
+
+/// Arguments controlling how the proof of work of a message is done.
+#[derive(Debug, structopt::StructOpt)]
+pub struct PowArgs {
+    /// Do the proof of work locally rather than on the node.
+    #[structopt(long, conflicts_with = "remote-pow")]
+    pub local_pow: bool,
+
+    /// Let the node do the proof of work.
+    #[structopt(long)]
+    pub remote_pow: bool,
+
+    /// Number of threads used for local proof of work (defaults to the number of CPUs).
+    #[structopt(long, value_name = "count", parse(try_from_str=try_pow_workers_from_str))]
+    pub pow_workers: Option<usize>,
+
+    /// Number of seconds to wait for the message to be sent, including its proof of work.
+    #[structopt(long, value_name = "seconds")]
+    pub pow_timeout: Option<u64>,
+}
+
+impl PowArgs {
+    /// Apply the arguments to the client options, taking precedence over the profile and the network.
+    pub fn apply_to(&self, options: &mut ClientOptions) {
+        if self.local_pow {
+            options.local_pow = Some(true);
+        } else if self.remote_pow {
+            options.local_pow = Some(false);
+        }
+        if self.pow_workers.is_some() {
+            options.pow_workers = self.pow_workers;
+        }
+        if self.pow_timeout.is_some() {
+            options.pow_timeout = self.pow_timeout;
+        }
+    }
+}
+
+/// Run a future that sends a message, giving up after the timeout (if any) and reporting the elapsed time on
+/// standard error while it runs if requested.
+pub async fn track_pow<T, F>(future: F, pow_timeout: Option<u64>, show_progress: bool) -> Result<T>
+where
+    F: Future<Output = Result<T>>,
+{
+    let start = Instant::now();
+    let progress = async {
+        tokio::pin!(future);
+        let mut ticks = interval(PROGRESS_INTERVAL);
+        // The first tick completes immediately.
+        ticks.tick().await;
+        loop {
+            tokio::select! {
+                result = &mut future => break result,
+                _ = ticks.tick(), if show_progress => {
+                    eprint!("\rSending message (proof of work included)... {}s", start.elapsed().as_secs());
+                    let _ = io::stderr().flush();
+                }
+            }
+        }
+    };
+
+    let result = match pow_timeout {
+        Some(t) => match timeout(Duration::from_secs(t), progress).await {
+            Ok(r) => r,
+            Err(_) => Err(Error::PowTimeout(t)),
+        },
+        None => progress.await,
+    };
+    if show_progress && start.elapsed() >= PROGRESS_INTERVAL {
+        eprintln!();
+    }
+
+    result
+}
+
+/// Number of trailing zero trits the hash of a message of the given size needs to reach the score.
+pub fn target_zeros(score: f64, message_size: usize) -> i32 {
+    ((message_size as f64 * score).ln() / LN_3).ceil().max(0.0) as i32
+}
+
+/// Average number of hashes needed to find a nonce for a message of the given size that reaches the score.
+pub fn expected_hashes(score: f64, message_size: usize) -> f64 {
+    3f64.powi(target_zeros(score, message_size))
+}
+
+/// The outcome of benchmarking local proof of work.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PowBenchView {
+    /// Number of threads the proof of work was done with.
+    pub workers: usize,
+    /// Number of nonces that were mined.
+    pub samples: usize,
+    pub elapsed_seconds: f64,
+    /// Estimated number of hashes computed per second over all threads.
+    pub hashes_per_second: f64,
+    /// Minimum proof of work score the estimate is made for.
+    pub min_pow_score: f64,
+    /// Size in bytes of the message the estimate is made for.
+    pub message_size: usize,
+    /// Estimated average number of seconds to do the proof of work for a single message.
+    pub estimated_seconds: f64,
+}
+
+/// Measure the local hash rate by mining nonces for random messages, and estimate how long the proof of work for a
+/// message of the given size takes at the score.
+pub async fn bench_pow(
+    workers: Option<usize>,
+    samples: usize,
+    score: f64,
+    message_size: usize,
+) -> Result<PowBenchView> {
+    let workers = match workers {
+        Some(w) => w,
+        None => thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+    };
+    let size = message_size.max(NONCE_BYTES + 1);
+    // The samples are mined for a fixed number of trailing zeros so that each takes long enough to be measured.
+    let sample_score = 2.0 * 3f64.powi(BENCH_TRAILING_ZEROS - 1) / size as f64;
+
+    let mined = tokio::task::spawn_blocking(move || {
+        let miner = MinerBuilder::new().with_num_workers(workers).finish();
+        let start = Instant::now();
+        for _ in 0..samples {
+            let bytes: Vec<u8> = (0..size - NONCE_BYTES).map(|_| rand::thread_rng().gen()).collect();
+            miner.nonce(&bytes, sample_score).ok()?;
+        }
+
+        Some(start.elapsed())
+    })
+    .await;
+    let elapsed = match mined {
+        Ok(Some(e)) => e,
+        _ => return Err(Error::CannotDoPow),
+    };
+
+    let hashes = samples as f64 * expected_hashes(sample_score, size);
+    let hashes_per_second = hashes / elapsed.as_secs_f64().max(f64::EPSILON);
+
+    Ok(PowBenchView {
+        workers,
+        samples,
+        elapsed_seconds: elapsed.as_secs_f64(),
+        hashes_per_second,
+        min_pow_score: score,
+        message_size,
+        estimated_seconds: expected_hashes(score, message_size) / hashes_per_second,
+    })
+}
+
+/// Get the minimum proof of work score required by the node.
+pub async fn get_min_pow_score(client: &ClientOptions) -> Result<f64> {
+    let iota = build_client(client).await?;
+
+    match iota.get_info().await {
+        Ok(ni) => Ok(ni.nodeinfo.min_pow_score),
+        Err(_) => Err(Error::CannotGetNodeInfo),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_zeros() {
+        assert_eq!(9, target_zeros(4000.0, 4));
+        assert_eq!(12, target_zeros(4000.0, 100));
+        assert_eq!(0, target_zeros(0.5, 1));
+        assert_eq!(3f64.powi(12), expected_hashes(4000.0, 100));
+    }
+
+    #[test]
+    fn test_pow_args_apply_to() {
+        let mut options = ClientOptions {
+            local_pow: Some(true),
+            pow_timeout: Some(120),
+            ..Default::default()
+        };
+        let args = PowArgs {
+            local_pow: false,
+            remote_pow: true,
+            pow_workers: Some(2),
+            pow_timeout: None,
+        };
+        args.apply_to(&mut options);
+        assert_eq!(Some(false), options.local_pow);
+        assert_eq!(Some(2), options.pow_workers);
+        assert_eq!(Some(120), options.pow_timeout);
+    }
+
+    #[test]
+    fn test_try_pow_score_from_str() {
+        assert_eq!(4000.0, try_pow_score_from_str("4000").unwrap());
+        assert_eq!(
+            Error::PowScoreInvalid(String::from("0")),
+            try_pow_score_from_str("0").unwrap_err()
+        );
+        assert!(try_pow_workers_from_str("0").is_err());
+        assert_eq!(5, try_pow_samples_from_str("5").unwrap());
+        assert_eq!(
+            Error::PowSamplesInvalid(String::from("0")),
+            try_pow_samples_from_str("0").unwrap_err()
+        );
+    }
+}
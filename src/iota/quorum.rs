@@ -0,0 +1,210 @@
+use std::future::Future;
+
+use futures::future::join_all;
+use serde_json::{json, Value};
+
+use crate::{
+    error::{Error, Result},
+    iota::{
+        client::{redact_url, ClientOptions, MAX_MILESTONE_LAG},
+        views::{AddressSummary, IndexSearchView, MessageView, NodeSummary},
+    },
+};
+
+pub(crate) fn try_quorum_from_str(arg: &str) -> Result<usize> {
+    match arg.parse::<usize>() {
+        Ok(q) if q > 0 => Ok(q),
+        _ => Err(Error::QuorumInvalid(arg.to_string())),
+    }
+}
+
+/// Arguments for reading a result from several nodes at once.
+#[derive(Debug, structopt::StructOpt)]
+pub struct QuorumArgs {
+    /// Number of nodes of the network (or of the given URLs) that must agree on the result.
+    #[structopt(long, parse(try_from_str=try_quorum_from_str))]
+    pub quorum: Option<usize>,
+}
+
+/// A result that nodes can agree on.
+pub trait Agreement {
+    /// The part of the result that nodes must agree on, leaving out what naturally differs between nodes.
+    fn agreement(&self) -> Value;
+
+    /// Whether the nodes that gave two agreements agree with each other, which by default requires them to be equal.
+    fn agrees(a: &Value, b: &Value) -> bool {
+        a == b
+    }
+}
+
+impl Agreement for NodeSummary {
+    fn agreement(&self) -> Value {
+        json!({
+            "network": self.network,
+            "latest_milestone_index": self.node.latest_milestone.index,
+        })
+    }
+
+    /// Nodes on the same network agree if their latest milestones are at most a few milestones apart, as nodes are
+    /// rarely asked at exactly the same moment.
+    fn agrees(a: &Value, b: &Value) -> bool {
+        let index = |v: &Value| v["latest_milestone_index"].as_u64().unwrap_or_default();
+        let (a_index, b_index) = (index(a), index(b));
+
+        a["network"] == b["network"] && a_index.max(b_index) - a_index.min(b_index) <= u64::from(MAX_MILESTONE_LAG)
+    }
+}
+
+impl Agreement for MessageView {
+    fn agreement(&self) -> Value {
+        json!({
+            "id": self.id,
+            "payload": self.payload,
+            "status": self.metadata.status,
+            "referenced_by_milestone_index": self.metadata.referenced_by_milestone_index,
+            "ledger_inclusion_state": self.metadata.ledger_inclusion_state,
+            "conflict_reason": self.metadata.conflict_reason,
+        })
+    }
+}
+
+impl Agreement for IndexSearchView {
+    fn agreement(&self) -> Value {
+        let mut message_ids = self.message_ids.clone();
+        message_ids.sort();
+        let messages: Option<Vec<Value>> = self
+            .messages
+            .as_ref()
+            .map(|m| m.iter().map(Agreement::agreement).collect());
+
+        json!({ "total": self.total, "message_ids": message_ids, "messages": messages })
+    }
+}
+
+impl Agreement for AddressSummary {
+    fn agreement(&self) -> Value {
+        json!({
+            "address": self.address,
+            "balance": self.balance,
+            "dust_allowed": self.dust_allowed,
+            "outputs": self.outputs,
+        })
+    }
+}
+
+/// Group agreeing answers together, returning the indexes of the answers in the largest group (the first to be given
+/// on a tie).
+fn tally(answers: &[Option<Value>], agrees: fn(&Value, &Value) -> bool) -> Vec<usize> {
+    let mut groups: Vec<(&Value, Vec<usize>)> = Vec::new();
+    for (i, answer) in answers.iter().enumerate() {
+        let answer = match answer {
+            Some(a) => a,
+            None => continue,
+        };
+        match groups.iter_mut().find(|(a, _)| agrees(a, answer)) {
+            Some((_, indexes)) => indexes.push(i),
+            None => groups.push((answer, vec![i])),
+        }
+    }
+
+    let mut largest: Vec<usize> = Vec::new();
+    for (_, indexes) in groups {
+        if indexes.len() > largest.len() {
+            largest = indexes;
+        }
+    }
+
+    largest
+}
+
+/// Send the same read to every node, accepting the result only if at least `quorum` nodes agree on it.
+///
+/// Nodes that disagree (or fail) are reported, along with what every node answered if no quorum is reached.
+pub async fn quorum_read<T, F, Fut>(options: &ClientOptions, quorum: usize, read: F) -> Result<T>
+where
+    T: Agreement,
+    F: Fn(ClientOptions) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    if quorum > options.urls.len() {
+        return Err(Error::QuorumTooLarge(quorum, options.urls.len()));
+    }
+
+    let reads = options.urls.iter().map(|url| {
+        let mut single = options.clone();
+        single.urls = vec![url.clone()];
+        read(single)
+    });
+    let results = join_all(reads).await;
+    let answers: Vec<Option<Value>> = results
+        .iter()
+        .map(|r| r.as_ref().ok().map(Agreement::agreement))
+        .collect();
+    let agreeing = tally(&answers, T::agrees);
+    let reached = agreeing.len() >= quorum;
+
+    for (i, (url, result)) in options.urls.iter().zip(&results).enumerate() {
+        match (result, &answers[i]) {
+            (Err(e), _) => eprintln!("warning: {} failed to answer: {}", redact_url(url), e),
+            (Ok(_), Some(answer)) if !reached => eprintln!("warning: {} answered: {}", redact_url(url), answer),
+            (Ok(_), Some(answer)) if !agreeing.contains(&i) => {
+                eprintln!(
+                    "warning: {} disagrees with the quorum, answering: {}",
+                    redact_url(url),
+                    answer
+                )
+            }
+            _ => {}
+        }
+    }
+    if !reached {
+        return Err(Error::QuorumNotReached(agreeing.len(), quorum));
+    }
+
+    match results.into_iter().nth(agreeing[0]) {
+        Some(Ok(r)) => Ok(r),
+        _ => Err(Error::Generic),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_quorum_from_str() {
+        assert_eq!(2, try_quorum_from_str("2").unwrap());
+        assert_eq!(
+            Error::QuorumInvalid(String::from("0")),
+            try_quorum_from_str("0").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_tally() {
+        let answers = vec![Some(json!(1)), None, Some(json!(2)), Some(json!(2))];
+        assert_eq!(vec![2, 3], tally(&answers, AddressSummary::agrees));
+
+        let answers = vec![Some(json!(1)), Some(json!(2))];
+        assert_eq!(vec![0], tally(&answers, AddressSummary::agrees));
+
+        assert!(tally(&[None, None], AddressSummary::agrees).is_empty());
+    }
+
+    #[test]
+    fn test_node_summary_agrees() {
+        let answer =
+            |network: &str, index: u32| json!({ "network": { "id": network }, "latest_milestone_index": index });
+
+        assert!(NodeSummary::agrees(&answer("mainnet", 100), &answer("mainnet", 102)));
+        assert!(!NodeSummary::agrees(&answer("mainnet", 100), &answer("mainnet", 110)));
+        assert!(!NodeSummary::agrees(&answer("mainnet", 100), &answer("devnet", 100)));
+
+        let answers = vec![
+            Some(answer("mainnet", 100)),
+            Some(answer("mainnet", 101)),
+            Some(answer("mainnet", 50)),
+        ];
+        assert_eq!(vec![0, 1], tally(&answers, NodeSummary::agrees));
+    }
+}
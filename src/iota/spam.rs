@@ -0,0 +1,234 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use rand::Rng;
+use tokio::{
+    sync::Mutex,
+    time::{interval, Interval, MissedTickBehavior},
+};
+
+use iota_client::bee_message::MessageId;
+use serde::Serialize;
+
+use crate::{
+    error::Result,
+    iota::{
+        client::{build_client, ClientOptions},
+        send_message,
+    },
+};
+
+/// Placeholder replaced with the sequence number of the message.
+pub const SEQ_PLACEHOLDER: &str = "{seq}";
+
+/// Placeholder replaced with 16 random bytes, hex-encoded.
+pub const RAND_PLACEHOLDER: &str = "{rand}";
+
+/// Placeholder replaced with the current UNIX timestamp in milliseconds.
+pub const TIMESTAMP_PLACEHOLDER: &str = "{timestamp}";
+
+/// Render an index or data template for the message with the given sequence number.
+pub fn render_template(template: &str, seq: u64) -> String {
+    let mut rendered = template.replace(SEQ_PLACEHOLDER, &seq.to_string());
+    if rendered.contains(TIMESTAMP_PLACEHOLDER) {
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        rendered = rendered.replace(TIMESTAMP_PLACEHOLDER, &millis.to_string());
+    }
+    while rendered.contains(RAND_PLACEHOLDER) {
+        let bytes: [u8; 16] = rand::thread_rng().gen();
+        rendered = rendered.replacen(RAND_PLACEHOLDER, &hex::encode(bytes), 1);
+    }
+
+    rendered
+}
+
+/// Options controlling how many messages are spammed and how quickly.
+#[derive(Debug)]
+pub struct SpamOptions {
+    /// Total number of messages to send, unbounded if `None`.
+    pub count: Option<u64>,
+    /// Maximum amount of time to keep sending messages, unbounded if `None`.
+    pub duration: Option<Duration>,
+    /// Target number of messages per second, unlimited if `None`.
+    pub rate: Option<f64>,
+    /// Number of messages allowed to be in flight at once.
+    pub concurrency: usize,
+    /// Template for the indexation key of each message.
+    pub index: String,
+    /// Template for the data of each message.
+    pub data: String,
+}
+
+/// Results gathered from a spam run.
+#[derive(Debug, Default)]
+pub struct SpamReport {
+    pub successes: u64,
+    pub failures: u64,
+    pub latencies: Vec<Duration>,
+    pub elapsed: Duration,
+}
+
+impl SpamReport {
+    /// The latency under which the given percentage (0-100) of successful messages were sent.
+    pub fn percentile(&self, p: f64) -> Option<Duration> {
+        if self.latencies.is_empty() {
+            return None;
+        }
+
+        let mut sorted = self.latencies.clone();
+        sorted.sort();
+        let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+
+        Some(sorted[rank.clamp(1, sorted.len()) - 1])
+    }
+
+    /// The achieved number of successfully sent messages per second.
+    pub fn messages_per_second(&self) -> f64 {
+        match self.elapsed.as_secs_f64() {
+            s if s > 0.0 => self.successes as f64 / s,
+            _ => 0.0,
+        }
+    }
+
+    /// Summarize the report with latency percentiles and throughput.
+    pub fn summary(&self) -> SpamSummary {
+        let millis = |p: f64| self.percentile(p).map(|l| l.as_millis() as u64);
+
+        SpamSummary {
+            sent: self.successes + self.failures,
+            successes: self.successes,
+            failures: self.failures,
+            elapsed_seconds: self.elapsed.as_secs_f64(),
+            latency_ms: LatencySummary {
+                p50: millis(50.0),
+                p90: millis(90.0),
+                p99: millis(99.0),
+            },
+            messages_per_second: self.messages_per_second(),
+        }
+    }
+}
+
+/// Latency percentiles in milliseconds, absent if no message was sent successfully.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct LatencySummary {
+    pub p50: Option<u64>,
+    pub p90: Option<u64>,
+    pub p99: Option<u64>,
+}
+
+/// Summary of a spam run.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SpamSummary {
+    pub sent: u64,
+    pub successes: u64,
+    pub failures: u64,
+    pub elapsed_seconds: f64,
+    pub latency_ms: LatencySummary,
+    pub messages_per_second: f64,
+}
+
+/// Callback invoked with the sequence number, result, and latency of each message as it is sent.
+pub type SpamCallback = Arc<dyn Fn(u64, &Result<MessageId>, Duration) + Send + Sync>;
+
+/// Repeatedly broadcast data messages to a specific IOTA network.
+pub async fn spam_messages(options: SpamOptions, client: &ClientOptions, on_sent: SpamCallback) -> Result<SpamReport> {
+    let iota = Arc::new(build_client(client).await?);
+    let options = Arc::new(options);
+    let next_seq = Arc::new(AtomicU64::new(0));
+    let report = Arc::new(Mutex::new(SpamReport::default()));
+    let limiter: Option<Arc<Mutex<Interval>>> = options.rate.map(|r| {
+        let mut i = interval(Duration::from_secs_f64(1.0 / r));
+        i.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        Arc::new(Mutex::new(i))
+    });
+
+    let start = Instant::now();
+    let deadline = options.duration.map(|d| start + d);
+
+    let workers = (0..options.concurrency.max(1)).map(|_| {
+        let iota = iota.clone();
+        let options = options.clone();
+        let next_seq = next_seq.clone();
+        let report = report.clone();
+        let limiter = limiter.clone();
+        let on_sent = on_sent.clone();
+
+        tokio::spawn(async move {
+            loop {
+                if let Some(l) = &limiter {
+                    l.lock().await.tick().await;
+                }
+                if deadline.map_or(false, |d| Instant::now() >= d) {
+                    break;
+                }
+                let seq = next_seq.fetch_add(1, Ordering::SeqCst);
+                if options.count.map_or(false, |c| seq >= c) {
+                    break;
+                }
+
+                let index = render_template(&options.index, seq);
+                let data = render_template(&options.data, seq);
+                let sent_at = Instant::now();
+                let result = send_message(&iota, &index, data.as_bytes()).await;
+                let latency = sent_at.elapsed();
+                on_sent(seq, &result, latency);
+
+                let mut report = report.lock().await;
+                match result {
+                    Ok(_) => {
+                        report.successes += 1;
+                        report.latencies.push(latency);
+                    }
+                    Err(_) => report.failures += 1,
+                }
+            }
+        })
+    });
+    futures::future::join_all(workers).await;
+
+    let mut report = std::mem::take(&mut *report.lock().await);
+    report.elapsed = start.elapsed();
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template() {
+        assert_eq!("tio-spam-42", render_template("tio-spam-{seq}", 42));
+        assert_eq!("no placeholders", render_template("no placeholders", 0));
+
+        let rendered = render_template("{rand}-{rand}", 0);
+        let parts: Vec<&str> = rendered.split('-').collect();
+        assert_eq!(2, parts.len());
+        assert_eq!(32, parts[0].len());
+        assert_ne!(parts[0], parts[1]);
+    }
+
+    #[test]
+    fn test_percentile() {
+        let report = SpamReport {
+            successes: 4,
+            failures: 0,
+            latencies: vec![4, 1, 3, 2].into_iter().map(Duration::from_millis).collect(),
+            elapsed: Duration::from_secs(2),
+        };
+
+        assert_eq!(Some(Duration::from_millis(2)), report.percentile(50.0));
+        assert_eq!(Some(Duration::from_millis(4)), report.percentile(99.0));
+        assert_eq!(2.0, report.messages_per_second());
+        assert_eq!(None, SpamReport::default().percentile(50.0));
+    }
+}
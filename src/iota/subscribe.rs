@@ -0,0 +1,146 @@
+use chrono::Local;
+use iota_client::{BrokerOptions, MqttEvent, Topic, TopicEvent};
+use serde::Serialize;
+use tokio::sync::mpsc;
+
+use crate::{
+    error::{Error, Result},
+    iota::client::{client_builder, ClientOptions},
+};
+
+/// A stream of events that can be subscribed to on a node.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SubscriptionTopic {
+    /// Every new milestone.
+    LatestMilestones,
+    /// Every milestone once it is confirmed.
+    ConfirmedMilestones,
+    /// Every new message.
+    Messages,
+    /// New messages with the given indexation key.
+    Index(Vec<u8>),
+    /// Changes to the metadata of the message with the given (hex-encoded) ID.
+    Metadata(String),
+    /// Outputs created on or spent from the given Bech32-encoded address.
+    AddressOutputs(String),
+}
+
+impl SubscriptionTopic {
+    /// The topic as named by the node's event API.
+    pub fn path(&self) -> String {
+        match self {
+            Self::LatestMilestones => String::from("milestones/latest"),
+            Self::ConfirmedMilestones => String::from("milestones/confirmed"),
+            Self::Messages => String::from("messages"),
+            Self::Index(index) => format!("messages/indexation/{}", hex::encode(index)),
+            Self::Metadata(id) => format!("messages/{}/metadata", id),
+            Self::AddressOutputs(address) => format!("addresses/{}/outputs", address),
+        }
+    }
+}
+
+/// An event received for a topic, with the UNIX timestamp (in seconds) at which it arrived.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SubscriptionEvent {
+    pub timestamp: i64,
+    pub topic: String,
+    /// The event as sent by the node, which is kept as a string if it is not JSON.
+    pub payload: serde_json::Value,
+}
+
+impl From<&TopicEvent> for SubscriptionEvent {
+    fn from(event: &TopicEvent) -> Self {
+        let payload = match serde_json::from_str(&event.payload) {
+            Ok(v) => v,
+            Err(_) => serde_json::Value::String(event.payload.clone()),
+        };
+
+        Self {
+            timestamp: Local::now().timestamp(),
+            topic: event.topic.clone(),
+            payload,
+        }
+    }
+}
+
+/// Subscribe to topics on a node, calling `on_event` for every event until interrupted with Ctrl+C.
+///
+/// The connection to the node's MQTT broker is re-established whenever it drops.
+pub async fn subscribe<F: FnMut(SubscriptionEvent)>(
+    topics: &[SubscriptionTopic],
+    client: &ClientOptions,
+    mut on_event: F,
+) -> Result<()> {
+    // Quiet topics (e.g. an address without activity) must not be mistaken for a dead connection.
+    let broker_options = BrokerOptions::new()
+        .automatic_disconnect(false)
+        .max_reconnection_attempts(0);
+    let mut iota = match client_builder(client)
+        .await?
+        .with_mqtt_broker_options(broker_options)
+        .finish()
+        .await
+    {
+        Ok(c) => c,
+        Err(_) => return Err(Error::CannotBuildNodeClient),
+    };
+
+    let mut mqtt_topics = Vec::with_capacity(topics.len());
+    for topic in topics {
+        match Topic::new(topic.path()) {
+            Ok(t) => mqtt_topics.push(t),
+            Err(_) => return Err(Error::CannotSubscribe),
+        }
+    }
+
+    // Events arrive on the client's own task, so they are handed over to be handled here.
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+    let subscribed = iota
+        .subscriber()
+        .with_topics(mqtt_topics)
+        .subscribe(move |event: &TopicEvent| {
+            let _ = sender.send(SubscriptionEvent::from(event));
+        })
+        .await;
+    if subscribed.is_err() {
+        return Err(Error::CannotSubscribe);
+    }
+
+    let mut mqtt_events = iota.mqtt_event_receiver();
+    let mut disconnected = false;
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+    loop {
+        tokio::select! {
+            _ = &mut ctrl_c => break,
+            Some(event) = receiver.recv() => on_event(event),
+            Ok(()) = mqtt_events.changed() => {
+                let connected = matches!(*mqtt_events.borrow(), MqttEvent::Connected);
+                if !connected && !disconnected {
+                    eprintln!("warning: disconnected from the node's event stream, reconnecting...");
+                } else if connected && disconnected {
+                    eprintln!("info: reconnected to the node's event stream");
+                }
+                disconnected = !connected;
+            }
+        }
+    }
+
+    let _ = iota.subscriber().disconnect().await;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subscription_topic_path() {
+        assert_eq!("milestones/confirmed", SubscriptionTopic::ConfirmedMilestones.path());
+        assert_eq!(
+            "messages/indexation/74696f2d636c69",
+            SubscriptionTopic::Index(b"tio-cli".to_vec()).path()
+        );
+    }
+}
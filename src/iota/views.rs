@@ -0,0 +1,707 @@
+use std::{convert::TryFrom, str::FromStr};
+
+use iota_client::{
+    bee_message::{
+        address::{Address, Ed25519Address},
+        input::Input,
+        output::Output,
+        payload::Payload,
+        prelude::{
+            Essence, IndexationPayload, MigratedFundsEntry, MilestonePayload, ReceiptPayload, TransactionPayload,
+            TreasuryTransactionPayload,
+        },
+    },
+    bee_rest_api::types::{
+        dtos::{AddressDto, LedgerInclusionStateDto, OutputDto},
+        responses::{MessageMetadataResponse, OutputResponse},
+    },
+};
+use rsa::RsaPrivateKey;
+use serde::{Serialize, Serializer};
+
+use crate::{crypto, error::Error};
+
+/// Display data as text if it is valid UTF-8, otherwise as `0x`-prefixed hex.
+pub(crate) fn display_data(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(s) => s.to_string(),
+        Err(_) => format!("0x{}", hex::encode(bytes)),
+    }
+}
+
+fn serialize_data<S: Serializer>(bytes: &[u8], serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_str(&display_data(bytes))
+}
+
+/// How bytes such as indexation data are displayed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DataFormat {
+    /// Text if the bytes are valid UTF-8, otherwise hex (along with a hexdump in text output).
+    Auto,
+    Utf8,
+    Hex,
+    Base64,
+    Hexdump,
+    Json,
+    Cbor,
+    MessagePack,
+}
+
+impl Default for DataFormat {
+    fn default() -> Self {
+        Self::Auto
+    }
+}
+
+/// Format bytes as a hexdump of 16 bytes per line, with their offset and printable ASCII characters.
+pub(crate) fn hexdump(bytes: &[u8]) -> String {
+    let lines: Vec<String> = bytes
+        .chunks(16)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let hex: Vec<String> = chunk.iter().map(|b| format!("{:02x}", b)).collect();
+            let ascii: String = chunk
+                .iter()
+                .map(|&b| {
+                    if b == b' ' || b.is_ascii_graphic() {
+                        b as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+
+            format!("{:08x}  {:<47}  |{}|", i * 16, hex.join(" "), ascii)
+        })
+        .collect();
+
+    lines.join("\n")
+}
+
+/// Decode bytes holding structured data in the given format, where only JSON objects and arrays are detected
+/// automatically.
+pub(crate) fn decode_structured_data(bytes: &[u8], format: DataFormat) -> Option<serde_json::Value> {
+    match format {
+        DataFormat::Auto => match serde_json::from_slice(bytes) {
+            Ok(v @ serde_json::Value::Object(_)) | Ok(v @ serde_json::Value::Array(_)) => Some(v),
+            _ => None,
+        },
+        DataFormat::Json => serde_json::from_slice(bytes).ok(),
+        DataFormat::Cbor => serde_cbor::from_slice(bytes).ok(),
+        DataFormat::MessagePack => rmp_serde::from_slice(bytes).ok(),
+        DataFormat::Utf8 | DataFormat::Hex | DataFormat::Base64 | DataFormat::Hexdump => None,
+    }
+}
+
+/// Format bytes in the given format, replacing invalid UTF-8 sequences when displayed as text.
+///
+/// Structured data that cannot be decoded is displayed as if no format was given.
+pub(crate) fn format_data(bytes: &[u8], format: DataFormat) -> String {
+    match format {
+        DataFormat::Auto => display_data(bytes),
+        DataFormat::Json | DataFormat::Cbor | DataFormat::MessagePack => match decode_structured_data(bytes, format) {
+            Some(v) => v.to_string(),
+            None => display_data(bytes),
+        },
+        DataFormat::Utf8 => String::from_utf8_lossy(bytes).into_owned(),
+        DataFormat::Hex => format!("0x{}", hex::encode(bytes)),
+        DataFormat::Base64 => base64::encode(bytes),
+        DataFormat::Hexdump => hexdump(bytes),
+    }
+}
+
+/// Raw bytes along with the format to display them in.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DataView {
+    pub bytes: Vec<u8>,
+    pub format: DataFormat,
+}
+
+impl DataView {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self {
+            bytes,
+            format: DataFormat::Auto,
+        }
+    }
+}
+
+impl Serialize for DataView {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        // A hexdump is only meant to be read, so it is serialized as plain hex instead.
+        let format = match self.format {
+            DataFormat::Hexdump => DataFormat::Hex,
+            f => f,
+        };
+
+        // Structured data that was explicitly asked for is embedded as is rather than as a string.
+        if let DataFormat::Json | DataFormat::Cbor | DataFormat::MessagePack = format {
+            if let Some(v) = decode_structured_data(&self.bytes, format) {
+                return v.serialize(serializer);
+            }
+        }
+
+        serializer.serialize_str(&format_data(&self.bytes, format))
+    }
+}
+
+/// A data message that has been broadcasted to the Tangle.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct BroadcastView {
+    pub id: String,
+    pub index: String,
+    #[serde(serialize_with = "serialize_data")]
+    pub data: Vec<u8>,
+    pub size: usize,
+    /// Name of the algorithm the data was encrypted with before being broadcasted, if any.
+    pub encryption: Option<String>,
+    /// Link to the message in the network's explorer, if the network has one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explorer_url: Option<String>,
+    /// Seconds taken to send the message once connected to the node, including its proof of work.
+    pub elapsed_seconds: f64,
+    /// The outcome of waiting for the message to be confirmed, if requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmation: Option<ConfirmationView>,
+}
+
+/// The messages found on the Tangle for an index.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct IndexSearchView {
+    pub index: DataView,
+    /// Number of message IDs the node knows for the index, which may exceed the number shown.
+    pub total: usize,
+    pub message_ids: Vec<String>,
+    /// The messages themselves, if they were fetched.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub messages: Option<Vec<MessageView>>,
+}
+
+/// A message found on the Tangle along with its payload.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct MessageView {
+    pub id: String,
+    pub payload: PayloadView,
+    pub metadata: MetadataView,
+}
+
+/// The outcome of waiting for a message to be confirmed.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ConfirmationView {
+    pub id: String,
+    /// The message (either the original or a reattachment) that was referenced by a milestone.
+    pub confirmed_id: Option<String>,
+    pub status: MessageStatus,
+    /// Metadata of the referenced message, or of the latest attachment if none was referenced.
+    pub metadata: Option<MetadataView>,
+    pub promotions: Vec<String>,
+    pub reattachments: Vec<String>,
+    pub elapsed_seconds: f64,
+}
+
+/// What happened to a message that was requested to be promoted or reattached.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RemedyOutcome {
+    Promoted,
+    Reattached,
+    Skipped,
+    Failed,
+}
+
+/// The outcome of promoting or reattaching a message.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct RemedyView {
+    pub id: String,
+    pub outcome: RemedyOutcome,
+    /// ID of the new message that was attached, if any.
+    pub new_id: Option<String>,
+    /// Why the message was skipped or the action failed.
+    pub reason: Option<String>,
+}
+
+/// Whether a message has been confirmed by a milestone.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageStatus {
+    /// The message is known to the node but not yet referenced by a milestone.
+    Pending,
+    /// The message is referenced by a milestone and its transaction (if any) is applied to the ledger.
+    Confirmed,
+    /// The message is referenced by a milestone but its transaction conflicts with the ledger.
+    Conflicting,
+}
+
+/// The state of a message as known by the node.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct MetadataView {
+    pub status: MessageStatus,
+    pub parents: Vec<String>,
+    pub is_solid: bool,
+    pub referenced_by_milestone_index: Option<u32>,
+    /// UNIX timestamp (in seconds) of the referencing milestone, if it could be retrieved.
+    pub milestone_timestamp: Option<u64>,
+    /// One of "included", "conflicting" or "no_transaction" once referenced by a milestone.
+    pub ledger_inclusion_state: Option<String>,
+    pub conflict_reason: Option<String>,
+    pub should_promote: Option<bool>,
+    pub should_reattach: Option<bool>,
+}
+
+fn conflict_reason(code: u8) -> Option<String> {
+    let reason = match code {
+        0 => return None,
+        1 => "input already spent",
+        2 => "input already spent in the same milestone",
+        3 => "input not found",
+        4 => "input and output amounts do not match",
+        5 => "invalid signature",
+        6 => "invalid dust allowance",
+        255 => "semantic validation failed",
+        c => return Some(format!("unknown ({})", c)),
+    };
+
+    Some(reason.to_string())
+}
+
+impl MetadataView {
+    pub fn new(metadata: &MessageMetadataResponse, milestone_timestamp: Option<u64>) -> Self {
+        let ledger_inclusion_state = metadata.ledger_inclusion_state.as_ref().map(|s| match s {
+            LedgerInclusionStateDto::Included => "included",
+            LedgerInclusionStateDto::Conflicting => "conflicting",
+            LedgerInclusionStateDto::NoTransaction => "no_transaction",
+        });
+        let status = match (metadata.referenced_by_milestone_index, ledger_inclusion_state) {
+            (None, _) => MessageStatus::Pending,
+            (Some(_), Some("conflicting")) => MessageStatus::Conflicting,
+            (Some(_), _) => MessageStatus::Confirmed,
+        };
+
+        Self {
+            status,
+            parents: metadata.parent_message_ids.clone(),
+            is_solid: metadata.is_solid,
+            referenced_by_milestone_index: metadata.referenced_by_milestone_index,
+            milestone_timestamp,
+            ledger_inclusion_state: ledger_inclusion_state.map(String::from),
+            conflict_reason: metadata.conflict_reason.and_then(conflict_reason),
+            should_promote: metadata.should_promote,
+            should_reattach: metadata.should_reattach,
+        }
+    }
+}
+
+/// The supported payloads of a message.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PayloadView {
+    Indexation(IndexationView),
+    Transaction(TransactionView),
+    Milestone(MilestoneView),
+    Receipt(ReceiptView),
+    TreasuryTransaction(TreasuryTransactionView),
+}
+
+/// An indexation (data) payload.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct IndexationView {
+    pub index: DataView,
+    pub data: DataView,
+    pub size: usize,
+    /// Whether the data was decrypted after being fetched.
+    pub decrypted: bool,
+}
+
+/// A transaction (UTXO) payload, optionally containing a nested payload.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TransactionView {
+    pub inputs: Vec<InputView>,
+    pub outputs: Vec<OutputView>,
+    pub payload: Option<Box<PayloadView>>,
+}
+
+/// A milestone payload issued by the coordinator, optionally containing a receipt.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct MilestoneView {
+    pub index: u32,
+    pub timestamp: u64,
+    pub parents: Vec<String>,
+    pub merkle_proof: String,
+    pub public_keys: Vec<String>,
+    pub signatures: Vec<String>,
+    pub receipt: Option<Box<PayloadView>>,
+}
+
+/// A receipt payload listing the funds migrated from the legacy network.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ReceiptView {
+    pub migrated_at: u32,
+    pub last: bool,
+    pub funds: Vec<MigratedFundsView>,
+    pub transaction: Box<PayloadView>,
+}
+
+/// Funds migrated from a legacy bundle to an address.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct MigratedFundsView {
+    pub tail_transaction_hash: String,
+    pub address: AddressView,
+    pub amount: u64,
+}
+
+/// A treasury transaction payload, moving funds out of the treasury.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TreasuryTransactionView {
+    pub input: InputView,
+    pub output: OutputView,
+}
+
+/// An input of a transaction.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InputView {
+    Utxo {
+        output_id: String,
+        /// The output that is spent, if it was resolved.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        output: Option<LedgerOutputView>,
+    },
+    Treasury {
+        milestone_id: String,
+    },
+}
+
+/// An output of a transaction.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OutputView {
+    SignatureLockedSingle { address: AddressView, amount: u64 },
+    SignatureLockedDustAllowance { address: AddressView, amount: u64 },
+    Treasury { amount: u64 },
+}
+
+/// An address that outputs can be locked to.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AddressView {
+    Ed25519 { address: String },
+}
+
+impl From<&Address> for AddressView {
+    fn from(address: &Address) -> Self {
+        match address {
+            Address::Ed25519(a) => Self::Ed25519 { address: a.to_string() },
+        }
+    }
+}
+
+impl From<&Input> for InputView {
+    fn from(input: &Input) -> Self {
+        match input {
+            Input::Utxo(i) => Self::Utxo {
+                output_id: i.to_string(),
+                output: None,
+            },
+            Input::Treasury(i) => Self::Treasury {
+                milestone_id: i.milestone_id().to_string(),
+            },
+        }
+    }
+}
+
+impl From<&Output> for OutputView {
+    fn from(output: &Output) -> Self {
+        match output {
+            Output::SignatureLockedSingle(o) => Self::SignatureLockedSingle {
+                address: o.address().into(),
+                amount: o.amount(),
+            },
+            Output::SignatureLockedDustAllowance(o) => Self::SignatureLockedDustAllowance {
+                address: o.address().into(),
+                amount: o.amount(),
+            },
+            Output::Treasury(o) => Self::Treasury { amount: o.amount() },
+        }
+    }
+}
+
+impl From<&IndexationPayload> for IndexationView {
+    fn from(payload: &IndexationPayload) -> Self {
+        Self {
+            index: DataView::new(payload.index().to_vec()),
+            data: DataView::new(payload.data().to_vec()),
+            size: payload.index().len() + payload.data().len(),
+            decrypted: false,
+        }
+    }
+}
+
+impl TryFrom<&TransactionPayload> for TransactionView {
+    type Error = Error;
+
+    fn try_from(payload: &TransactionPayload) -> Result<Self, Self::Error> {
+        match payload.essence() {
+            Essence::Regular(e) => {
+                let inputs = e.inputs().iter().map(InputView::from).collect();
+                let outputs = e.outputs().iter().map(OutputView::from).collect();
+                let payload = match e.payload() {
+                    Some(p) => Some(Box::new(PayloadView::try_from(p)?)),
+                    None => None,
+                };
+
+                Ok(Self {
+                    inputs,
+                    outputs,
+                    payload,
+                })
+            }
+        }
+    }
+}
+
+impl TryFrom<&MilestonePayload> for MilestoneView {
+    type Error = Error;
+
+    fn try_from(payload: &MilestonePayload) -> Result<Self, Self::Error> {
+        let essence = payload.essence();
+        let receipt = match essence.receipt() {
+            Some(r) => Some(Box::new(PayloadView::try_from(r)?)),
+            None => None,
+        };
+
+        Ok(Self {
+            index: *essence.index(),
+            timestamp: essence.timestamp(),
+            parents: essence.parents().iter().map(|p| p.to_string()).collect(),
+            merkle_proof: hex::encode(essence.merkle_proof()),
+            public_keys: essence.public_keys().iter().map(hex::encode).collect(),
+            signatures: payload.signatures().iter().map(hex::encode).collect(),
+            receipt,
+        })
+    }
+}
+
+impl From<&MigratedFundsEntry> for MigratedFundsView {
+    fn from(entry: &MigratedFundsEntry) -> Self {
+        Self {
+            tail_transaction_hash: entry.tail_transaction_hash().to_string(),
+            address: entry.output().address().into(),
+            amount: entry.output().amount(),
+        }
+    }
+}
+
+impl TryFrom<&ReceiptPayload> for ReceiptView {
+    type Error = Error;
+
+    fn try_from(payload: &ReceiptPayload) -> Result<Self, Self::Error> {
+        Ok(Self {
+            migrated_at: *payload.migrated_at(),
+            last: payload.last(),
+            funds: payload.funds().iter().map(MigratedFundsView::from).collect(),
+            transaction: Box::new(PayloadView::try_from(payload.transaction())?),
+        })
+    }
+}
+
+impl From<&TreasuryTransactionPayload> for TreasuryTransactionView {
+    fn from(payload: &TreasuryTransactionPayload) -> Self {
+        Self {
+            input: payload.input().into(),
+            output: payload.output().into(),
+        }
+    }
+}
+
+impl PayloadView {
+    /// Decrypt the data of every encrypted indexation payload (including nested ones) with the given key.
+    pub fn decrypt_with(&mut self, private_key: &RsaPrivateKey) -> Result<(), Error> {
+        match self {
+            Self::Indexation(p) if crypto::is_envelope(&p.data.bytes) => {
+                p.data.bytes = crypto::decrypt(&p.data.bytes, private_key)?;
+                p.decrypted = true;
+
+                Ok(())
+            }
+            Self::Indexation(_) => Ok(()),
+            Self::Transaction(p) => match p.payload.as_mut() {
+                Some(inner) => inner.decrypt_with(private_key),
+                None => Ok(()),
+            },
+            Self::Milestone(_) | Self::Receipt(_) | Self::TreasuryTransaction(_) => Ok(()),
+        }
+    }
+
+    /// Set the formats to display the index and data of every indexation payload (including nested ones) in.
+    pub fn set_data_formats(&mut self, index_format: DataFormat, data_format: DataFormat) {
+        match self {
+            Self::Indexation(p) => {
+                p.index.format = index_format;
+                p.data.format = data_format;
+            }
+            Self::Transaction(p) => {
+                if let Some(inner) = p.payload.as_mut() {
+                    inner.set_data_formats(index_format, data_format);
+                }
+            }
+            Self::Milestone(_) | Self::Receipt(_) | Self::TreasuryTransaction(_) => {}
+        }
+    }
+
+    /// The data of the indexation payload (which may be nested in a transaction), if any.
+    pub fn indexation_data(&self) -> Option<&[u8]> {
+        match self {
+            Self::Indexation(p) => Some(&p.data.bytes),
+            Self::Transaction(p) => p.payload.as_ref().and_then(|inner| inner.indexation_data()),
+            Self::Milestone(_) | Self::Receipt(_) | Self::TreasuryTransaction(_) => None,
+        }
+    }
+}
+
+impl TryFrom<&Payload> for PayloadView {
+    type Error = Error;
+
+    fn try_from(payload: &Payload) -> Result<Self, Self::Error> {
+        match payload {
+            Payload::Indexation(p) => Ok(Self::Indexation(p.as_ref().into())),
+            Payload::Transaction(p) => Ok(Self::Transaction(TransactionView::try_from(p.as_ref())?)),
+            Payload::Milestone(p) => Ok(Self::Milestone(MilestoneView::try_from(p.as_ref())?)),
+            Payload::Receipt(p) => Ok(Self::Receipt(ReceiptView::try_from(p.as_ref())?)),
+            Payload::TreasuryTransaction(p) => Ok(Self::TreasuryTransaction(p.as_ref().into())),
+        }
+    }
+}
+
+/// Information about a node and the network it belongs to.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct NodeSummary {
+    pub network: NetworkSummary,
+    pub node: NodeDetails,
+}
+
+/// Information about an IOTA network.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct NetworkSummary {
+    pub id: String,
+    pub bech32_hrp: String,
+}
+
+/// Information about a particular node.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct NodeDetails {
+    pub url: String,
+    pub name: String,
+    pub version: String,
+    pub referenced_messages_per_second: f64,
+    pub referenced_rate: f64,
+    pub latest_milestone: MilestoneSummary,
+}
+
+/// The index and UNIX timestamp (in seconds) of a milestone.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct MilestoneSummary {
+    pub index: u32,
+    pub timestamp: u64,
+}
+
+/// A milestone along with the changes it applied to the ledger.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct MilestoneDetails {
+    pub index: u32,
+    pub message_id: String,
+    pub timestamp: u64,
+    /// IDs of the outputs created by the transactions the milestone confirmed.
+    pub created_outputs: Vec<String>,
+    /// IDs of the outputs spent by the transactions the milestone confirmed.
+    pub consumed_outputs: Vec<String>,
+    /// The receipt of migrated funds included in the milestone, if any.
+    pub receipt: Option<PayloadView>,
+}
+
+/// The balance and a page of the outputs of an address.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct AddressSummary {
+    pub address: String,
+    pub balance: u64,
+    pub dust_allowed: bool,
+    pub ledger_index: u32,
+    pub outputs: OutputPage,
+}
+
+/// A page of outputs, where `page` starts at 1.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct OutputPage {
+    pub page: usize,
+    pub page_size: usize,
+    /// Number of outputs across all pages.
+    pub total: usize,
+    pub outputs: Vec<LedgerOutputView>,
+}
+
+/// An output in the ledger along with the message that created it.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct LedgerOutputView {
+    pub output_id: String,
+    pub message_id: String,
+    pub is_spent: bool,
+    /// One of "signature_locked_single", "signature_locked_dust_allowance" or "treasury".
+    pub kind: String,
+    /// Bech32-encoded address the output is locked to (treasury outputs have none).
+    pub address: Option<String>,
+    pub amount: u64,
+}
+
+impl LedgerOutputView {
+    pub fn new(output_id: String, response: &OutputResponse, bech32_hrp: &str) -> Self {
+        let bech32 = |address: &AddressDto| match address {
+            AddressDto::Ed25519(a) => match Ed25519Address::from_str(&a.address) {
+                Ok(a) => Some(Address::Ed25519(a).to_bech32(bech32_hrp)),
+                Err(_) => None,
+            },
+        };
+        let (kind, address, amount) = match &response.output {
+            OutputDto::SignatureLockedSingle(o) => ("signature_locked_single", bech32(&o.address), o.amount),
+            OutputDto::SignatureLockedDustAllowance(o) => {
+                ("signature_locked_dust_allowance", bech32(&o.address), o.amount)
+            }
+            OutputDto::Treasury(o) => ("treasury", None, o.amount),
+        };
+
+        Self {
+            output_id,
+            message_id: response.message_id.clone(),
+            is_spent: response.is_spent,
+            kind: kind.to_string(),
+            address,
+            amount,
+        }
+    }
+}
+
+/// Activity observed on an address while monitoring it, with UNIX timestamps (in seconds).
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum AddressEvent {
+    /// The initial state of the address.
+    Snapshot {
+        timestamp: i64,
+        outputs: usize,
+        balance: u64,
+        dust_allowed: bool,
+    },
+    /// An output was created on the address.
+    NewOutput {
+        timestamp: i64,
+        output_id: String,
+        amount: u64,
+        message_id: String,
+    },
+    /// An output of the address was spent.
+    SpentOutput { timestamp: i64, output_id: String },
+    /// The balance of the address changed.
+    BalanceChanged {
+        timestamp: i64,
+        previous_balance: u64,
+        balance: u64,
+    },
+}
@@ -1,4 +1,9 @@
 pub mod cli;
 pub mod commands;
+pub mod config;
+pub mod crypto;
 pub mod error;
 pub mod iota;
+pub mod keys;
+pub mod output;
+pub mod secret;
@@ -1,14 +1,21 @@
 use structopt::StructOpt;
 
-use tio::{
-    cli::Command,
-    error::{Error, Result},
-};
+use tio::error::EXIT_INVALID_INPUT;
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    match tio::cli::Cli::from_args().run().await {
-        Ok(_) => Ok(()),
-        Err(_) => panic!("{:?}", Error::Generic),
+async fn main() {
+    let cli = match tio::cli::Cli::from_args_safe() {
+        Ok(c) => c,
+        // Help and version information are not errors, so let `clap` print them and exit successfully.
+        Err(e) if !e.use_stderr() => e.exit(),
+        Err(e) => {
+            eprintln!("{}", e.message);
+            std::process::exit(EXIT_INVALID_INPUT);
+        }
+    };
+
+    if let Err(e) = cli.run().await {
+        eprintln!("error: {}", e);
+        std::process::exit(e.exit_code());
     }
 }
@@ -0,0 +1,727 @@
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use serde::Serialize;
+
+use crate::{
+    error::{Error, Result},
+    iota::{
+        pow::PowBenchView,
+        spam::SpamSummary,
+        subscribe::SubscriptionEvent,
+        views::{
+            decode_structured_data, display_data, format_data, hexdump, AddressEvent, AddressSummary, AddressView,
+            BroadcastView, ConfirmationView, DataFormat, DataView, IndexSearchView, InputView, LedgerOutputView,
+            MessageStatus, MessageView, MetadataView, MilestoneDetails, NodeSummary, OutputView, PayloadView,
+            RemedyOutcome, RemedyView,
+        },
+    },
+    keys::KeyView,
+};
+
+/// The formats in which command output can be printed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OutputFormat {
+    /// Human-readable text.
+    Text,
+    /// A single machine-readable JSON document (or one document per line for streaming commands).
+    Json,
+}
+
+impl Default for OutputFormat {
+    fn default() -> Self {
+        Self::Text
+    }
+}
+
+pub(crate) fn try_output_format_from_str(arg: &str) -> Result<OutputFormat> {
+    match arg {
+        "text" => Ok(OutputFormat::Text),
+        "json" => Ok(OutputFormat::Json),
+        _ => Err(Error::OutputFormatInvalid(arg.to_string())),
+    }
+}
+
+/// Trait to implement for values that have a human-readable text representation.
+pub trait Render {
+    fn render(&self) -> String;
+}
+
+impl OutputFormat {
+    /// Print a command's result as text or as a pretty-printed JSON document.
+    pub fn print<T: Render + Serialize>(&self, value: &T) -> Result<()> {
+        match self {
+            Self::Text => println!("{}", value.render()),
+            Self::Json => match serde_json::to_string_pretty(value) {
+                Ok(json) => println!("{}", json),
+                Err(_) => return Err(Error::Generic),
+            },
+        }
+
+        Ok(())
+    }
+
+    /// Print a single event as text or as a JSON document on its own line.
+    pub fn print_event<T: Render + Serialize>(&self, value: &T) -> Result<()> {
+        match self {
+            Self::Text => println!("{}", value.render()),
+            Self::Json => match serde_json::to_string(value) {
+                Ok(json) => println!("{}", json),
+                Err(_) => return Err(Error::Generic),
+            },
+        }
+
+        Ok(())
+    }
+}
+
+fn format_timestamp(timestamp: i64) -> String {
+    let datetime: DateTime<Utc> = DateTime::from_utc(NaiveDateTime::from_timestamp(timestamp, 0), Utc);
+
+    datetime.format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+fn status_name(status: MessageStatus) -> &'static str {
+    match status {
+        MessageStatus::Pending => "pending",
+        MessageStatus::Confirmed => "confirmed",
+        MessageStatus::Conflicting => "conflicting",
+    }
+}
+
+fn format_local_timestamp(timestamp: i64) -> String {
+    Local.timestamp(timestamp, 0).format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+/// Render labelled data in its format, pretty-printing structured data and showing a hexdump below binary data
+/// that is displayed automatically.
+fn render_data(label: &str, data: &DataView) -> String {
+    if let Some(v) = decode_structured_data(&data.bytes, data.format) {
+        let kind = match data.format {
+            DataFormat::Cbor => "CBOR",
+            DataFormat::MessagePack => "MessagePack",
+            _ => "JSON",
+        };
+        // Serializing a JSON value cannot fail.
+        let pretty = serde_json::to_string_pretty(&v).unwrap_or_default();
+
+        return format!("{} ({}):\n{}", label, kind, pretty);
+    }
+
+    match data.format {
+        DataFormat::Hexdump => format!("{}:\n{}", label, hexdump(&data.bytes)),
+        DataFormat::Auto if std::str::from_utf8(&data.bytes).is_err() => format!(
+            "{}: {}\n{}",
+            label,
+            format_data(&data.bytes, DataFormat::Hex),
+            hexdump(&data.bytes)
+        ),
+        f => format!("{}: {}", label, format_data(&data.bytes, f)),
+    }
+}
+
+impl Render for BroadcastView {
+    fn render(&self) -> String {
+        let mut lines = vec![
+            String::from("--- Data Message ---"),
+            format!("ID: {}", self.id),
+            format!("Index: {}", self.index),
+            format!("Data: {}", display_data(&self.data)),
+        ];
+        if let Some(encryption) = &self.encryption {
+            lines.push(format!("Encryption: {}", encryption));
+        }
+        lines.push(format!("Size: {} byte(s)", self.size));
+        lines.push(format!("Sent in: {:.2}s", self.elapsed_seconds));
+        if let Some(explorer_url) = &self.explorer_url {
+            lines.push(format!("Explorer: {}", explorer_url));
+        }
+        if let Some(confirmation) = &self.confirmation {
+            lines.push(format!("\n{}", confirmation.render()));
+        }
+
+        lines.join("\n")
+    }
+}
+
+impl Render for ConfirmationView {
+    fn render(&self) -> String {
+        let mut lines = vec![
+            String::from("--- Confirmation ---"),
+            format!("Status: {}", status_name(self.status)),
+        ];
+        if let Some(id) = &self.confirmed_id {
+            lines.push(format!("Confirmed ID: {}", id));
+        }
+        if let Some(index) = self.metadata.as_ref().and_then(|m| m.referenced_by_milestone_index) {
+            lines.push(format!("Milestone: {}", index));
+        }
+        if let Some(reason) = self.metadata.as_ref().and_then(|m| m.conflict_reason.as_ref()) {
+            lines.push(format!("Conflict Reason: {}", reason));
+        }
+        for id in &self.promotions {
+            lines.push(format!("Promoted: {}", id));
+        }
+        for id in &self.reattachments {
+            lines.push(format!("Reattached: {}", id));
+        }
+        lines.push(format!("Elapsed: {:.2}s", self.elapsed_seconds));
+
+        lines.join("\n")
+    }
+}
+
+impl Render for AddressView {
+    fn render(&self) -> String {
+        match self {
+            Self::Ed25519 { address } => format!("Address: {}, Type: Ed25519", address),
+        }
+    }
+}
+
+impl Render for InputView {
+    fn render(&self) -> String {
+        match self {
+            Self::Utxo {
+                output_id,
+                output: None,
+            } => output_id.clone(),
+            Self::Utxo {
+                output_id,
+                output: Some(o),
+            } => format!(
+                "{} ({}, Amount: {}i, from message {})",
+                output_id,
+                o.address.as_deref().unwrap_or("treasury"),
+                o.amount,
+                o.message_id,
+            ),
+            Self::Treasury { milestone_id } => format!("Treasury (from milestone {})", milestone_id),
+        }
+    }
+}
+
+impl Render for OutputView {
+    fn render(&self) -> String {
+        match self {
+            Self::SignatureLockedSingle { address, amount } => format!("{}, Amount: {}i", address.render(), amount),
+            Self::SignatureLockedDustAllowance { address, amount } => {
+                format!("{}, Amount: {}i (dust allowance)", address.render(), amount)
+            }
+            Self::Treasury { amount } => format!("Treasury, Amount: {}i", amount),
+        }
+    }
+}
+
+impl Render for PayloadView {
+    fn render(&self) -> String {
+        match self {
+            Self::Indexation(p) => format!(
+                "--- Data Payload ---\n\
+                {}\n\
+                {}\n\
+                Size: {} byte(s)",
+                render_data("Index", &p.index),
+                render_data(if p.decrypted { "Data (decrypted)" } else { "Data" }, &p.data),
+                p.size,
+            ),
+            Self::Transaction(p) => {
+                let mut lines = vec![String::from("--- UTXO Payload ---")];
+
+                if !p.inputs.is_empty() {
+                    lines.push(String::from("Input(s):"));
+                    lines.extend(p.inputs.iter().map(Render::render));
+                }
+
+                if !p.outputs.is_empty() {
+                    lines.push(String::from("\nOutput(s):"));
+                    lines.extend(p.outputs.iter().map(Render::render));
+                }
+
+                if let Some(payload) = &p.payload {
+                    lines.push(format!("\n{}", payload.render()));
+                }
+
+                lines.join("\n")
+            }
+            Self::Milestone(p) => {
+                let mut lines = vec![
+                    String::from("--- Milestone Payload ---"),
+                    format!("Index: {}", p.index),
+                    format!("Timestamp: {} UTC", format_timestamp(p.timestamp as i64)),
+                    String::from("Parent(s):"),
+                ];
+                lines.extend(p.parents.iter().map(|id| format!("  {}", id)));
+                lines.push(format!("Merkle Proof: {}", p.merkle_proof));
+                lines.push(String::from("Signature(s):"));
+                for (public_key, signature) in p.public_keys.iter().zip(&p.signatures) {
+                    lines.push(format!("  {} (key {})", signature, public_key));
+                }
+
+                if let Some(receipt) = &p.receipt {
+                    lines.push(format!("\n{}", receipt.render()));
+                }
+
+                lines.join("\n")
+            }
+            Self::Receipt(p) => {
+                let mut lines = vec![
+                    String::from("--- Receipt Payload ---"),
+                    format!("Migrated At: milestone {}", p.migrated_at),
+                    format!("Final: {}", if p.last { "yes" } else { "no" }),
+                    String::from("Migrated Funds:"),
+                ];
+                for funds in &p.funds {
+                    lines.push(format!(
+                        "{}, Amount: {}i (tail transaction {})",
+                        funds.address.render(),
+                        funds.amount,
+                        funds.tail_transaction_hash,
+                    ));
+                }
+                lines.push(format!("\n{}", p.transaction.render()));
+
+                lines.join("\n")
+            }
+            Self::TreasuryTransaction(p) => format!(
+                "--- Treasury Transaction Payload ---\n\
+                Input: {}\n\
+                Output: {}",
+                p.input.render(),
+                p.output.render(),
+            ),
+        }
+    }
+}
+
+impl Render for MessageView {
+    fn render(&self) -> String {
+        format!("{}\n\n{}", self.payload.render(), self.metadata.render())
+    }
+}
+
+impl Render for MetadataView {
+    fn render(&self) -> String {
+        let status = status_name(self.status);
+        let yes_no = |b: bool| if b { "yes" } else { "no" };
+
+        let mut lines = vec![String::from("--- Metadata ---"), format!("Status: {}", status)];
+        lines.push(String::from("Parent(s):"));
+        lines.extend(self.parents.iter().map(|p| format!("  {}", p)));
+        lines.push(format!("Solid: {}", yes_no(self.is_solid)));
+        if let Some(index) = self.referenced_by_milestone_index {
+            match self.milestone_timestamp {
+                Some(t) => lines.push(format!("Milestone: {} ({} UTC)", index, format_timestamp(t as i64))),
+                None => lines.push(format!("Milestone: {}", index)),
+            }
+        }
+        if let Some(state) = &self.ledger_inclusion_state {
+            lines.push(format!("Ledger Inclusion: {}", state.replace('_', " ")));
+        }
+        if let Some(reason) = &self.conflict_reason {
+            lines.push(format!("Conflict Reason: {}", reason));
+        }
+        if self.should_promote == Some(true) {
+            lines.push(String::from("The node suggests promoting the message"));
+        }
+        if self.should_reattach == Some(true) {
+            lines.push(String::from("The node suggests reattaching the message"));
+        }
+
+        lines.join("\n")
+    }
+}
+
+impl Render for IndexSearchView {
+    fn render(&self) -> String {
+        let mut lines = vec![
+            String::from("--- Index Search ---"),
+            render_data("Index", &self.index),
+            format!("Found: {} message(s), showing {}", self.total, self.message_ids.len()),
+        ];
+
+        match &self.messages {
+            Some(messages) => {
+                for message in messages {
+                    lines.push(format!("\nMessage: {}", message.id));
+                    lines.push(message.render());
+                }
+            }
+            None => lines.extend(self.message_ids.iter().map(|id| format!("  {}", id))),
+        }
+
+        lines.join("\n")
+    }
+}
+
+impl Render for Vec<RemedyView> {
+    fn render(&self) -> String {
+        let lines: Vec<String> = self
+            .iter()
+            .map(|r| match (r.outcome, &r.new_id, &r.reason) {
+                (RemedyOutcome::Promoted, Some(new_id), _) => format!("{}: promoted by {}", r.id, new_id),
+                (RemedyOutcome::Reattached, Some(new_id), _) => format!("{}: reattached as {}", r.id, new_id),
+                (RemedyOutcome::Failed, _, reason) => {
+                    format!("{}: failed ({})", r.id, reason.as_deref().unwrap_or("unknown reason"))
+                }
+                (_, _, reason) => format!("{}: skipped ({})", r.id, reason.as_deref().unwrap_or("not needed")),
+            })
+            .collect();
+
+        lines.join("\n")
+    }
+}
+
+impl Render for MilestoneDetails {
+    fn render(&self) -> String {
+        let mut lines = vec![
+            String::from("--- Milestone ---"),
+            format!("Index: {}", self.index),
+            format!("Message: {}", self.message_id),
+            format!("Timestamp: {} UTC", format_timestamp(self.timestamp as i64)),
+        ];
+
+        lines.push(format!("Created Output(s): {}", self.created_outputs.len()));
+        lines.extend(self.created_outputs.iter().map(|id| format!("  {}", id)));
+        lines.push(format!("Consumed Output(s): {}", self.consumed_outputs.len()));
+        lines.extend(self.consumed_outputs.iter().map(|id| format!("  {}", id)));
+
+        if let Some(receipt) = &self.receipt {
+            lines.push(format!("\n{}", receipt.render()));
+        }
+
+        lines.join("\n")
+    }
+}
+
+impl Render for Vec<MilestoneDetails> {
+    fn render(&self) -> String {
+        let milestones: Vec<String> = self.iter().map(Render::render).collect();
+
+        milestones.join("\n\n")
+    }
+}
+
+impl Render for LedgerOutputView {
+    fn render(&self) -> String {
+        format!(
+            "--- Output ---\n\
+            ID: {}\n\
+            Type: {}\n\
+            Address: {}\n\
+            Amount: {}i\n\
+            Spent: {}\n\
+            Message: {}",
+            self.output_id,
+            self.kind.replace('_', " "),
+            self.address.as_deref().unwrap_or("-"),
+            self.amount,
+            if self.is_spent { "yes" } else { "no" },
+            self.message_id,
+        )
+    }
+}
+
+impl Render for AddressSummary {
+    fn render(&self) -> String {
+        let page = &self.outputs;
+        let pages = page.total / page.page_size + usize::from(page.total % page.page_size != 0);
+
+        let mut lines = vec![
+            String::from("--- Address ---"),
+            format!("Address: {}", self.address),
+            format!("Balance: {}i", self.balance),
+            format!("Dust Allowed: {}", if self.dust_allowed { "yes" } else { "no" }),
+            format!("Ledger Index: {}", self.ledger_index),
+            format!(
+                "\n--- Outputs (page {} of {}, {} total) ---",
+                page.page,
+                pages.max(1),
+                page.total
+            ),
+        ];
+        if page.outputs.is_empty() {
+            lines.push(String::from("No outputs found"));
+        }
+        lines.extend(page.outputs.iter().map(|o| {
+            format!(
+                "{} | {}i | {} | from message {}",
+                o.output_id,
+                o.amount,
+                if o.is_spent { "spent" } else { "unspent" },
+                o.message_id,
+            )
+        }));
+
+        lines.join("\n")
+    }
+}
+
+impl Render for NodeSummary {
+    fn render(&self) -> String {
+        format!(
+            "--- Network Info ---\n\
+            ID: {}\n\
+            Bech32 HRP: {}\n\
+            \n--- Node Info ---\n\
+            URL: {}\n\
+            Software: {} {}\n\
+            Stats: {:.1} MPS @ {:.2}%\n\
+            Milestones: No. {} @ {}",
+            self.network.id,
+            self.network.bech32_hrp,
+            self.node.url,
+            self.node.name,
+            self.node.version,
+            self.node.referenced_messages_per_second,
+            self.node.referenced_rate,
+            self.node.latest_milestone.index,
+            format_timestamp(self.node.latest_milestone.timestamp as i64),
+        )
+    }
+}
+
+impl Render for SubscriptionEvent {
+    fn render(&self) -> String {
+        format!(
+            "[{}] {}: {}",
+            format_local_timestamp(self.timestamp),
+            self.topic,
+            self.payload
+        )
+    }
+}
+
+impl Render for AddressEvent {
+    fn render(&self) -> String {
+        match self {
+            Self::Snapshot {
+                timestamp,
+                outputs,
+                balance,
+                dust_allowed,
+            } => format!(
+                "[{}] Tracking {} output(s), balance: {}i (dust allowed: {})",
+                format_local_timestamp(*timestamp),
+                outputs,
+                balance,
+                dust_allowed,
+            ),
+            Self::NewOutput {
+                timestamp,
+                output_id,
+                amount,
+                message_id,
+            } => format!(
+                "[{}] New output: {} ({}i) from message {}",
+                format_local_timestamp(*timestamp),
+                output_id,
+                amount,
+                message_id,
+            ),
+            Self::SpentOutput { timestamp, output_id } => {
+                format!("[{}] Spent output: {}", format_local_timestamp(*timestamp), output_id)
+            }
+            Self::BalanceChanged {
+                timestamp,
+                previous_balance,
+                balance,
+            } => format!(
+                "[{}] Balance changed: {}i -> {}i",
+                format_local_timestamp(*timestamp),
+                previous_balance,
+                balance,
+            ),
+        }
+    }
+}
+
+impl Render for PowBenchView {
+    fn render(&self) -> String {
+        format!(
+            "--- Proof of Work ---\n\
+            Workers: {}\n\
+            Samples: {} in {:.2}s\n\
+            Hash rate: {:.0} hashes/s\n\
+            Minimum score: {}\n\
+            Message size: {} bytes\n\
+            Estimated time per message: {:.2}s",
+            self.workers,
+            self.samples,
+            self.elapsed_seconds,
+            self.hashes_per_second,
+            self.min_pow_score,
+            self.message_size,
+            self.estimated_seconds,
+        )
+    }
+}
+
+impl Render for KeyView {
+    fn render(&self) -> String {
+        let kind = match (self.private, self.protected) {
+            (true, true) => "private (passphrase protected)",
+            (true, false) => "private",
+            (false, _) => "public",
+        };
+
+        format!(
+            "--- Key ---\n\
+            Alias: {}\n\
+            Fingerprint: {}\n\
+            Size: {} bits\n\
+            Type: {}",
+            self.alias, self.fingerprint, self.bits, kind,
+        )
+    }
+}
+
+impl Render for Vec<KeyView> {
+    fn render(&self) -> String {
+        if self.is_empty() {
+            return String::from("No keys found");
+        }
+
+        self.iter().map(Render::render).collect::<Vec<_>>().join("\n\n")
+    }
+}
+
+impl Render for SpamSummary {
+    fn render(&self) -> String {
+        let latency = |l: Option<u64>| match l {
+            Some(ms) => format!("{} ms", ms),
+            None => String::from("n/a"),
+        };
+
+        format!(
+            "\n--- Spam Summary ---\n\
+            Sent: {} message(s) in {:.2}s\n\
+            Successes: {}\n\
+            Failures: {}\n\
+            Latency: p50 {}, p90 {}, p99 {}\n\
+            Throughput: {:.2} MPS",
+            self.sent,
+            self.elapsed_seconds,
+            self.successes,
+            self.failures,
+            latency(self.latency_ms.p50),
+            latency(self.latency_ms.p90),
+            latency(self.latency_ms.p99),
+            self.messages_per_second,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iota::views::{OutputPage, TreasuryTransactionView};
+
+    #[test]
+    fn test_try_output_format_from_str() {
+        assert_eq!(OutputFormat::Text, try_output_format_from_str("text").unwrap());
+        assert_eq!(OutputFormat::Json, try_output_format_from_str("json").unwrap());
+        assert_eq!(
+            Error::OutputFormatInvalid(String::from("xml")),
+            try_output_format_from_str("xml").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_render_broadcast_view() {
+        let view = BroadcastView {
+            id: String::from("830e3f2aeb7409b27683480e6edd0fe6c1f3c503486c31b5df5a0472b395433d"),
+            index: String::from("tio-cli"),
+            data: b"tio-message".to_vec(),
+            size: 18,
+            encryption: None,
+            explorer_url: None,
+            elapsed_seconds: 1.5,
+            confirmation: None,
+        };
+
+        assert!(view
+            .render()
+            .ends_with("Index: tio-cli\nData: tio-message\nSize: 18 byte(s)\nSent in: 1.50s"));
+    }
+
+    #[test]
+    fn test_render_address_summary_pages() {
+        let summary = |total, page_size| AddressSummary {
+            address: String::from("atoi1qzt0nhsf38nh6rs4p6zs5knqp6psgha9wsv74uajqgjmwc75ugupx3y7x0r"),
+            balance: 0,
+            dust_allowed: false,
+            ledger_index: 0,
+            outputs: OutputPage {
+                page: 1,
+                page_size,
+                total,
+                outputs: Vec::new(),
+            },
+        };
+
+        assert!(summary(41, 20).render().contains("(page 1 of 3, 41 total)"));
+        assert!(summary(40, 20).render().contains("(page 1 of 2, 40 total)"));
+        assert!(summary(0, 20).render().contains("(page 1 of 1, 0 total)"));
+        assert!(summary(5, usize::MAX).render().contains("(page 1 of 1, 5 total)"));
+    }
+
+    #[test]
+    fn test_render_binary_data() {
+        let data = DataView::new(vec![0x00, 0x74, 0x69, 0x6f, 0xff]);
+
+        assert_eq!(
+            "Data: 0x0074696fff\n00000000  00 74 69 6f ff                                   |.tio.|",
+            render_data("Data", &data)
+        );
+    }
+
+    #[test]
+    fn test_render_json_data() {
+        let data = DataView::new(br#"{"event":"login","ok":true}"#.to_vec());
+
+        assert_eq!(
+            "Data (JSON):\n{\n  \"event\": \"login\",\n  \"ok\": true\n}",
+            render_data("Data", &data)
+        );
+    }
+
+    #[test]
+    fn test_render_treasury_transaction_view() {
+        let view = PayloadView::TreasuryTransaction(TreasuryTransactionView {
+            input: InputView::Treasury {
+                milestone_id: String::from("0000000000000000000000000000000000000000000000000000000000000000"),
+            },
+            output: OutputView::Treasury { amount: 1000 },
+        });
+
+        assert_eq!(
+            "--- Treasury Transaction Payload ---\n\
+            Input: Treasury (from milestone 0000000000000000000000000000000000000000000000000000000000000000)\n\
+            Output: Treasury, Amount: 1000i",
+            view.render()
+        );
+    }
+
+    #[test]
+    fn test_render_metadata_view() {
+        let view = MetadataView {
+            status: MessageStatus::Conflicting,
+            parents: vec![String::from(
+                "830e3f2aeb7409b27683480e6edd0fe6c1f3c503486c31b5df5a0472b395433d",
+            )],
+            is_solid: true,
+            referenced_by_milestone_index: Some(1234),
+            milestone_timestamp: Some(0),
+            ledger_inclusion_state: Some(String::from("conflicting")),
+            conflict_reason: Some(String::from("input already spent")),
+            should_promote: None,
+            should_reattach: None,
+        };
+
+        assert!(view.render().starts_with("--- Metadata ---\nStatus: conflicting\n"));
+        assert!(view.render().ends_with(
+            "Milestone: 1234 (1970-01-01 00:00:00 UTC)\n\
+            Ledger Inclusion: conflicting\n\
+            Conflict Reason: input already spent"
+        ));
+    }
+}
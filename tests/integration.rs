@@ -5,24 +5,38 @@ Matthew Maxwell <maxwellmattryan@gmail.com>
 CLI tool for interacting with the IOTA Tangle
 
 USAGE:
-    tio <SUBCOMMAND>
+    tio [OPTIONS] <SUBCOMMAND>
 
 FLAGS:
     -h, --help       Prints help information
     -V, --version    Prints version information
 
+OPTIONS:
+    -o, --output <output>      Format of the command output ("text" or "json") [env: TIO_OUTPUT=]
+        --profile <profile>    Named profile from the configuration file to use [env: TIO_PROFILE=]
+
 SUBCOMMANDS:
+    address      Show the balance and outputs of an address in the IOTA ledger
     broadcast    Broadcast a message to the IOTA Tangle
     help         Prints this message or the help of the given subcommand(s)
     info         Query for node information on the IOTA Tangle
+    keys         Manage the RSA keys used to encrypt and decrypt data
+    milestone    Show milestones and the ledger changes they confirmed
+    monitor      Monitor an address for activity in the IOTA ledger
+    output       Show an output in the IOTA ledger given its ID
+    pow          Measure the proof of work done for messages
+    promote      Promote messages that are not yet confirmed
+    reattach     Reattach messages that are not yet confirmed
     search       Search for a message on the IOTA Tangle
+    spam         Repeatedly broadcast messages to the IOTA Tangle
+    subscribe    Stream events from a node as they happen
 ";
 
 const INVALID_COMMAND: &str =
     "error: Found argument 'bad-command' which wasn't expected, or isn't valid in this context
 
 USAGE:
-    tio <SUBCOMMAND>
+    tio [OPTIONS] <SUBCOMMAND>
 
 For more information try --help
 ";
@@ -31,7 +45,7 @@ const INVALID_SEARCH_ARGS: &str = "error: The following required arguments were
     <id>
 
 USAGE:
-    tio search [OPTIONS] <id>
+    tio search [FLAGS] [OPTIONS] [id]
 
 For more information try --help
 ";